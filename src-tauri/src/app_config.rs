@@ -0,0 +1,54 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Subset of the frontend's `config.json` the backend needs
+#[derive(Deserialize, Default, Clone)]
+#[serde(default, rename_all = "camelCase")]
+pub struct AppConfig {
+    pub paths: HashMap<String, String>,
+    pub categorized: Option<bool>,
+    /// Number of previous versions kept per mod for rollback
+    pub keep_versions: Option<usize>,
+    /// Keep previous versions as 7z archives instead of plain folders
+    pub compress_versions: Option<bool>,
+    /// Install mods into the central library and link them into the Mods folder
+    pub library_mode: Option<bool>,
+    /// Save every GameBanana gallery image with a mod instead of just the first
+    pub preview_gallery: Option<bool>,
+}
+
+/// Resolve a file or folder stored next to `config.json`
+pub fn app_path(name: &str) -> PathBuf {
+    let cwd = crate::get_cwd();
+    if cwd.is_empty() {
+        PathBuf::from(name)
+    } else {
+        PathBuf::from(cwd).join(name)
+    }
+}
+
+pub fn load_config() -> AppConfig {
+    std::fs::read_to_string(app_path("config.json"))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+/// Mods folder configured for a game
+pub fn mods_root(game: &str) -> Result<PathBuf, String> {
+    let config = load_config();
+    let root = config
+        .paths
+        .get(game)
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| format!("No mod directory configured for game '{}'", game))?;
+    if !root.is_dir() {
+        return Err(format!(
+            "Mod directory for game '{}' not found: {:?}",
+            game, root
+        ));
+    }
+    Ok(root)
+}
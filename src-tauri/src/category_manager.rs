@@ -0,0 +1,192 @@
+use crate::app_config::{load_config, mods_root};
+use crate::fs_utils::move_path;
+use crate::library_store::{create_dir_link, is_link, remove_link, resolve_link};
+use crate::manifest::{read_manifest, write_manifest};
+use crate::mod_manager::{
+    disabled_name, enabled_name, is_disabled_name, mod_key, relative_path, scan_mods,
+    toggle_result, unique_path, ToggleResult,
+};
+use crate::profile_manager::rename_profile_key;
+use crate::user_state::migrate_user_state;
+use crate::version_store::{load_versions, move_versions};
+use std::path::{Path, PathBuf};
+
+/// Characters Windows does not allow in file names
+const INVALID_NAME_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Check a mod or category name can be used as a folder name
+pub fn folder_name(name: &str) -> Result<String, String> {
+    // Windows drops trailing dots and spaces on its own
+    let name = name.trim().trim_end_matches('.');
+    if name.is_empty() || name.contains(|c: char| INVALID_NAME_CHARS.contains(&c) || c.is_control())
+    {
+        return Err(format!("Invalid folder name: '{}'", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Game and Mods folder a mod sits in
+fn locate(mod_dir: &Path) -> Result<(String, PathBuf), String> {
+    load_config()
+        .paths
+        .into_iter()
+        .filter(|(_, root)| !root.is_empty())
+        .map(|(game, root)| (game, PathBuf::from(root)))
+        .filter(|(_, root)| mod_dir.starts_with(root) && mod_dir != root)
+        .max_by_key(|(_, root)| root.components().count())
+        .ok_or_else(|| format!("{:?} is not inside a configured Mods folder", mod_dir))
+}
+
+/// Move a mod folder to `dest`, taking its library copy, saved variables, profiles and manifest along
+fn relocate_mod(
+    game: &str,
+    root: &Path,
+    mod_dir: &Path,
+    dest: &Path,
+    category: Option<&str>,
+) -> Result<PathBuf, String> {
+    if !mod_dir.is_dir() {
+        return Err(format!("Mod folder not found: {:?}", mod_dir));
+    }
+    let old_key = mod_key(root, mod_dir);
+    let new_key = mod_key(root, dest);
+    if is_link(mod_dir) {
+        // Library copies are stored under their key, so they move together with the link
+        let stored = resolve_link(mod_dir);
+        let depth = relative_path(root, mod_dir).split('/').count();
+        let library = stored
+            .ancestors()
+            .nth(depth)
+            .ok_or_else(|| format!("Library copy of {:?} not found", mod_dir))?;
+        let new_stored = library.join(&new_key);
+        if new_stored != stored {
+            let same_folder = new_stored
+                .to_string_lossy()
+                .eq_ignore_ascii_case(&stored.to_string_lossy());
+            if new_stored.exists() && !same_folder {
+                return Err(format!("{:?} is already in the library", new_stored));
+            }
+            move_path(&stored, &new_stored)?;
+        }
+        remove_link(mod_dir)?;
+        create_dir_link(&new_stored, dest)?;
+    } else {
+        move_path(mod_dir, dest)?;
+    }
+    println!("Moved {:?} -> {:?}", mod_dir, dest);
+    // Drop the category folder the mod leaves behind if nothing else is in it
+    if let Some(old_parent) = mod_dir.parent().filter(|p| *p != root) {
+        if crate::is_directory_empty(old_parent).unwrap_or(false) {
+            let _ = std::fs::remove_dir(old_parent);
+        }
+    }
+
+    if let Err(e) = migrate_user_state(mod_dir, dest) {
+        println!("Failed to move saved variables of {:?}: {}", mod_dir, e);
+    }
+    if let Err(e) = rename_profile_key(game, &old_key, &new_key) {
+        println!("Failed to update profiles for {}: {}", new_key, e);
+    }
+    if let Err(e) = move_versions(game, &old_key, &new_key) {
+        println!("Failed to move retained versions of {}: {}", new_key, e);
+    }
+    if let Some(mut manifest) = read_manifest(dest) {
+        if let Some(name) = dest.file_name().and_then(|n| n.to_str()) {
+            manifest.name = enabled_name(name);
+        }
+        if let Some(category) = category.filter(|c| !c.is_empty()) {
+            manifest.category = category.to_string();
+        }
+        manifest.versions = load_versions(dest, &manifest);
+        write_manifest(dest, &manifest)?;
+    }
+    Ok(dest.to_path_buf())
+}
+
+/// Move a mod into another category folder; an empty category moves it to the Mods folder itself
+fn move_to_category(
+    game: &str,
+    root: &Path,
+    mod_dir: &Path,
+    category: &str,
+) -> Result<PathBuf, String> {
+    let name = mod_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {:?}", mod_dir))?;
+    let category = if category.is_empty() {
+        String::new()
+    } else {
+        folder_name(category)?
+    };
+    let parent = root.join(&category);
+    if mod_dir.parent() == Some(parent.as_path()) {
+        return Ok(mod_dir.to_path_buf());
+    }
+    let dest = unique_path(&parent, name, false);
+    relocate_mod(game, root, mod_dir, &dest, Some(&category))
+}
+
+/// Move a mod to another category, returning its new path
+#[tauri::command]
+pub fn move_mod(path: String, category: String) -> Result<String, String> {
+    let mod_dir = Path::new(&path);
+    let (game, root) = locate(mod_dir)?;
+    move_to_category(&game, &root, mod_dir, category.trim())
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// Rename a mod folder, keeping its DISABLED marker, and return its new path
+#[tauri::command]
+pub fn rename_mod(path: String, new_name: String) -> Result<String, String> {
+    let mod_dir = Path::new(&path);
+    let (game, root) = locate(mod_dir)?;
+    let old_name = mod_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {:?}", mod_dir))?;
+    let new_name = folder_name(&enabled_name(&new_name))?;
+    let target_name = if is_disabled_name(old_name) {
+        disabled_name(&new_name)
+    } else {
+        new_name
+    };
+    if target_name == old_name {
+        return Ok(path);
+    }
+    let parent = mod_dir
+        .parent()
+        .ok_or_else(|| format!("Path has no parent folder: {:?}", mod_dir))?;
+    // A change of case only is a rename of the same folder, not a collision
+    let dest = if target_name.eq_ignore_ascii_case(old_name) {
+        parent.join(&target_name)
+    } else {
+        unique_path(parent, &target_name, false)
+    };
+    relocate_mod(&game, &root, mod_dir, &dest, None).map(|p| p.to_string_lossy().to_string())
+}
+
+/// Move every mod into the category folder recorded in its manifest,
+/// for Mods folders installed flat before the `categorized` setting was turned on
+#[tauri::command]
+pub fn recategorize_mods(game: String) -> Result<Vec<ToggleResult>, String> {
+    let root = mods_root(&game)?;
+    let mut results = Vec::new();
+    for installed in scan_mods(&root) {
+        let Some(category) = installed
+            .manifest
+            .as_ref()
+            .map(|m| m.category.trim().to_string())
+            .filter(|c| !c.is_empty())
+        else {
+            continue;
+        };
+        if installed.category.eq_ignore_ascii_case(&category) {
+            continue;
+        }
+        let result = move_to_category(&game, &root, Path::new(&installed.path), &category);
+        results.push(toggle_result(installed.path, result));
+    }
+    println!("Re-categorized {} mods of {}", results.len(), game);
+    Ok(results)
+}
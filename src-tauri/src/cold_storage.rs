@@ -0,0 +1,283 @@
+use crate::app_config::{app_path, mods_root};
+use crate::fs_utils::{path_size, replace_dir, walk_files};
+use crate::manifest::{is_imi_file, now_secs, read_manifest, write_manifest};
+use crate::mod_manager::{enabled_name, is_disabled_name, scan_mods, unique_path};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Archives of mods moved to cold storage
+const COLD_DIR: &str = "cold_storage";
+/// Marks a mod folder whose files live in cold storage; the folder keeps only IMI's own files
+pub const COLD_STUB_FILE: &str = "imi_cold_storage.json";
+const PROGRESS_POLL: Duration = Duration::from_millis(250);
+
+/// What a cold-storage stub records about its archive
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ColdStub {
+    pub archive: String,
+    /// Size of the mod folder before archiving
+    pub original_size: u64,
+    pub archived_size: u64,
+    pub archived: u64,
+    pub file_count: usize,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ColdMod {
+    pub path: String,
+    pub key: String,
+    pub stub: ColdStub,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ColdStorageReport {
+    pub archived: Vec<ColdMod>,
+    pub original_size: u64,
+    pub archived_size: u64,
+    /// Bytes freed by archiving
+    pub saved: u64,
+    /// Disabled mods still on disk, which could be archived
+    pub disabled_count: usize,
+    pub disabled_size: u64,
+}
+
+pub fn is_archived(mod_dir: &Path) -> bool {
+    mod_dir.join(COLD_STUB_FILE).is_file()
+}
+
+pub fn read_stub(mod_dir: &Path) -> Option<ColdStub> {
+    let data = std::fs::read_to_string(mod_dir.join(COLD_STUB_FILE)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn write_stub(mod_dir: &Path, stub: &ColdStub) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(stub).map_err(|e| e.to_string())?;
+    std::fs::write(mod_dir.join(COLD_STUB_FILE), data)
+        .map_err(|e| format!("Failed to write cold storage stub: {}", e))
+}
+
+/// Emit progress under `key` from the growing size of `watched` until the returned closure is called.
+/// 7z gives no progress of its own, so this is all there is to go on.
+fn watch_progress(
+    app_handle: &tauri::AppHandle,
+    key: &str,
+    watched: &Path,
+    total: u64,
+) -> impl FnOnce() {
+    let done = Arc::new(AtomicBool::new(false));
+    let watcher = {
+        let done = done.clone();
+        let app_handle = app_handle.clone();
+        let watched = watched.to_path_buf();
+        let key = key.to_string();
+        std::thread::spawn(move || {
+            let start_time = Instant::now();
+            while !done.load(Ordering::SeqCst) {
+                crate::emit_progress(&app_handle, &key, path_size(&watched), total, start_time);
+                std::thread::sleep(PROGRESS_POLL);
+            }
+        })
+    };
+    move || {
+        done.store(true, Ordering::SeqCst);
+        let _ = watcher.join();
+    }
+}
+
+/// Disabled by name in the Mods folder, or an unlinked mod in the library
+fn is_disabled_mod(mod_dir: &Path) -> bool {
+    let name = mod_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    is_disabled_name(name)
+        || (mod_dir.starts_with(app_path("library"))
+            && read_manifest(mod_dir).is_some_and(|m| !m.enabled))
+}
+
+/// Pack a disabled mod into cold storage and strip its folder down to a stub,
+/// emitting progress under `key` against the uncompressed size
+async fn archive_one(
+    app_handle: &tauri::AppHandle,
+    mod_dir: &Path,
+    key: &str,
+) -> Result<ColdStub, String> {
+    if !mod_dir.is_dir() || crate::library_store::is_link(mod_dir) {
+        return Err(format!("Mod folder not found: {:?}", mod_dir));
+    }
+    if is_archived(mod_dir) {
+        return Err(format!("{:?} is already in cold storage", mod_dir));
+    }
+    if !is_disabled_mod(mod_dir) {
+        return Err(format!("Only disabled mods can be archived: {:?}", mod_dir));
+    }
+    let name = mod_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("mod");
+    let store = app_path(COLD_DIR);
+    std::fs::create_dir_all(&store).map_err(|e| format!("Failed to create {:?}: {}", store, e))?;
+    let archive = unique_path(
+        &store,
+        &format!("{}_{}.7z", now_secs(), enabled_name(name)),
+        true,
+    );
+    let original_size = path_size(mod_dir);
+    let file_count = walk_files(mod_dir).len();
+    let stop = watch_progress(app_handle, key, &archive, original_size);
+    let result = crate::compress_dir(app_handle, mod_dir, &archive).await;
+    stop();
+    result?;
+    let archived_size = path_size(&archive);
+    if archived_size == 0 {
+        let _ = std::fs::remove_file(&archive);
+        return Err(format!("Failed to archive {:?}: empty archive", mod_dir));
+    }
+
+    // Keep the manifest and preview so the mod still lists normally
+    let entries = std::fs::read_dir(mod_dir).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let rel = entry.file_name().to_string_lossy().to_string();
+        if path.is_file() && is_imi_file(&rel) {
+            continue;
+        }
+        let result = if path.is_dir() {
+            std::fs::remove_dir_all(&path)
+        } else {
+            std::fs::remove_file(&path)
+        };
+        result.map_err(|e| format!("Failed to remove {:?}: {}", path, e))?;
+    }
+    let stub = ColdStub {
+        archive: archive.to_string_lossy().to_string(),
+        original_size,
+        archived_size,
+        archived: now_secs(),
+        file_count,
+    };
+    write_stub(mod_dir, &stub)?;
+    crate::emit_progress(
+        app_handle,
+        key,
+        original_size,
+        original_size,
+        Instant::now(),
+    );
+    println!(
+        "Archived {:?}: {} -> {}",
+        mod_dir,
+        crate::format_bytes(original_size),
+        crate::format_bytes(archived_size)
+    );
+    Ok(stub)
+}
+
+/// Unpack an archived mod back into its folder, emitting extraction progress under `key`
+pub async fn restore_archived(
+    app_handle: &tauri::AppHandle,
+    mod_dir: &Path,
+    key: &str,
+) -> Result<(), String> {
+    let stub = read_stub(mod_dir).ok_or_else(|| format!("{:?} is not in cold storage", mod_dir))?;
+    let staging = app_path("downloads").join(format!("cold_restore_{}", now_secs()));
+    let _ = std::fs::remove_dir_all(&staging);
+
+    let stop = watch_progress(app_handle, key, &staging, stub.original_size);
+    let result = async {
+        crate::decompress_file(
+            app_handle.clone(),
+            &stub.archive,
+            &staging.to_string_lossy(),
+        )
+        .await?;
+        // The stub's manifest is newer than the archived one
+        if let Some(manifest) = read_manifest(mod_dir) {
+            write_manifest(&staging, &manifest)?;
+        }
+        replace_dir(mod_dir, &staging)
+    }
+    .await;
+    stop();
+    let _ = std::fs::remove_dir_all(&staging);
+    result?;
+    crate::emit_progress(
+        app_handle,
+        key,
+        stub.original_size,
+        stub.original_size,
+        Instant::now(),
+    );
+
+    let _ = std::fs::remove_file(mod_dir.join(COLD_STUB_FILE));
+    if let Err(e) = std::fs::remove_file(&stub.archive) {
+        println!("Failed to remove archive {}: {}", stub.archive, e);
+    }
+    println!("Restored {:?} from cold storage", mod_dir);
+    Ok(())
+}
+
+/// Restore a mod about to be enabled if it sits in cold storage
+pub async fn ensure_restored(app_handle: &tauri::AppHandle, mod_dir: &Path) -> Result<(), String> {
+    if is_archived(mod_dir) {
+        restore_archived(app_handle, mod_dir, &mod_dir.to_string_lossy()).await?;
+    }
+    Ok(())
+}
+
+/// Move a disabled mod into cold storage, leaving a stub folder with its manifest;
+/// progress is emitted under the mod path
+#[tauri::command]
+pub async fn archive_mod(app_handle: tauri::AppHandle, path: String) -> Result<ColdStub, String> {
+    archive_one(&app_handle, Path::new(&path), &path).await
+}
+
+/// Unpack an archived mod without enabling it; progress is emitted under the mod path
+#[tauri::command]
+pub async fn restore_mod(app_handle: tauri::AppHandle, path: String) -> Result<(), String> {
+    restore_archived(&app_handle, Path::new(&path), &path).await
+}
+
+/// Disk usage of cold storage for a game, including the library, and what archiving the rest would save
+#[tauri::command]
+pub fn get_cold_storage_usage(game: String) -> Result<ColdStorageReport, String> {
+    let root = mods_root(&game)?;
+    let mut mods = scan_mods(&root);
+    mods.extend(scan_mods(&crate::library_store::library_root(&game)));
+    let mut report = ColdStorageReport::default();
+    for installed in mods {
+        let mod_dir = PathBuf::from(&installed.path);
+        if crate::library_store::is_link(&mod_dir) {
+            continue;
+        }
+        match read_stub(&mod_dir) {
+            Some(stub) => {
+                report.original_size += stub.original_size;
+                report.archived_size += stub.archived_size;
+                report.archived.push(ColdMod {
+                    path: installed.path,
+                    key: installed.key,
+                    stub,
+                });
+            }
+            None if is_disabled_mod(&mod_dir) => {
+                report.disabled_count += 1;
+                report.disabled_size += path_size(&mod_dir);
+            }
+            None => {}
+        }
+    }
+    report.saved = report.original_size.saturating_sub(report.archived_size);
+    println!(
+        "Cold storage for {}: {} mods, {} saved, {} more in {} disabled mods",
+        game,
+        report.archived.len(),
+        crate::format_bytes(report.saved),
+        crate::format_bytes(report.disabled_size),
+        report.disabled_count
+    );
+    Ok(report)
+}
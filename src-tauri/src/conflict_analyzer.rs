@@ -0,0 +1,128 @@
+use crate::ini_parser::read_ini;
+use crate::mod_manager::{active_inis, relative_path, scan_mods};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Section kinds whose `hash` XXMI matches against game resources
+const OVERRIDE_KINDS: &[&str] = &["TextureOverride", "ShaderOverride"];
+
+/// One override section that targets a hash
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HashOverride {
+    pub mod_path: String,
+    pub mod_name: String,
+    pub ini: String,
+    pub section: String,
+    pub line: usize,
+}
+
+/// Overrides from more than one mod that target the same hash
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HashConflict {
+    pub hash: String,
+    pub kind: String,
+    /// `match_first_index` of TextureOverride sections; overrides of different index ranges don't collide
+    pub match_first_index: Option<String>,
+    pub mods: Vec<String>,
+    pub sections: Vec<HashOverride>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictGroup {
+    pub category: String,
+    pub conflicts: Vec<HashConflict>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ConflictReport {
+    pub mods_scanned: usize,
+    pub groups: Vec<ConflictGroup>,
+}
+
+/// Report hashes overridden by more than one enabled mod of a game, grouped by category
+#[tauri::command]
+pub fn find_hash_conflicts(game: String) -> Result<ConflictReport, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    let mut report = ConflictReport::default();
+    // (kind, hash, match_first_index) -> overrides, plus the category of every mod involved
+    let mut overrides: BTreeMap<(String, String, Option<String>), Vec<HashOverride>> =
+        BTreeMap::new();
+    let mut categories: BTreeMap<String, String> = BTreeMap::new();
+
+    for installed in scan_mods(&root).into_iter().filter(|m| m.enabled) {
+        report.mods_scanned += 1;
+        let mod_dir = std::path::PathBuf::from(&installed.path);
+        categories.insert(installed.path.clone(), installed.category_name());
+
+        for ini in active_inis(&mod_dir) {
+            let doc = match read_ini(&ini) {
+                Ok(doc) => doc,
+                Err(e) => {
+                    println!("{}", e);
+                    continue;
+                }
+            };
+            for section in &doc.sections {
+                let Some(kind) = OVERRIDE_KINDS.iter().find(|k| section.is_kind(k)) else {
+                    continue;
+                };
+                let Some(hash) = section.get("hash").map(|h| h.to_ascii_lowercase()) else {
+                    continue;
+                };
+                let first_index = section
+                    .get("match_first_index")
+                    .map(|v| v.trim().to_string());
+                overrides
+                    .entry((kind.to_string(), hash, first_index))
+                    .or_default()
+                    .push(HashOverride {
+                        mod_path: installed.path.clone(),
+                        mod_name: installed.name.clone(),
+                        ini: relative_path(&mod_dir, &ini),
+                        section: section.name.clone(),
+                        line: section.line,
+                    });
+            }
+        }
+    }
+
+    let mut groups: BTreeMap<String, Vec<HashConflict>> = BTreeMap::new();
+    for ((kind, hash, match_first_index), sections) in overrides {
+        let mods: BTreeSet<&String> = sections.iter().map(|s| &s.mod_path).collect();
+        if mods.len() < 2 {
+            continue;
+        }
+        let category = mods
+            .iter()
+            .filter_map(|m| categories.get(*m))
+            .find(|c| !c.is_empty())
+            .cloned()
+            .unwrap_or_default();
+        let mod_names: BTreeSet<String> = sections.iter().map(|s| s.mod_name.clone()).collect();
+        groups.entry(category).or_default().push(HashConflict {
+            hash,
+            kind,
+            match_first_index,
+            mods: mod_names.into_iter().collect(),
+            sections,
+        });
+    }
+    report.groups = groups
+        .into_iter()
+        .map(|(category, conflicts)| ConflictGroup {
+            category,
+            conflicts,
+        })
+        .collect();
+    println!(
+        "Hash conflict scan for {}: {} mods, {} categories with conflicts",
+        game,
+        report.mods_scanned,
+        report.groups.len()
+    );
+    Ok(report)
+}
@@ -0,0 +1,641 @@
+const DDS_MAGIC: &[u8; 4] = b"DDS ";
+const HEADER_END: usize = 128;
+const DX10_HEADER_END: usize = 148;
+const DDPF_FOURCC: u32 = 0x4;
+const DDPF_RGB: u32 = 0x40;
+/// Largest side D3D11 allows for a 2D texture; anything bigger is a corrupt header
+const MAX_DIMENSION: usize = 16384;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Format {
+    Bc1,
+    Bc3,
+    Bc7,
+    Rgba8,
+    Bgra8,
+}
+
+impl Format {
+    fn from_dxgi(dxgi: u32) -> Option<Self> {
+        match dxgi {
+            70..=72 => Some(Format::Bc1),
+            76..=78 => Some(Format::Bc3),
+            97..=99 => Some(Format::Bc7),
+            27..=29 => Some(Format::Rgba8),
+            87 | 90 | 91 => Some(Format::Bgra8),
+            _ => None,
+        }
+    }
+
+    /// Bytes per 4x4 block, or per pixel for uncompressed formats
+    fn unit_size(self) -> usize {
+        match self {
+            Format::Bc1 => 8,
+            Format::Bc3 | Format::Bc7 => 16,
+            Format::Rgba8 | Format::Bgra8 => 4,
+        }
+    }
+
+    fn is_compressed(self) -> bool {
+        !matches!(self, Format::Rgba8 | Format::Bgra8)
+    }
+
+    /// Bytes of one mip level, or nothing if that does not fit in memory
+    fn level_size(self, width: usize, height: usize) -> Option<usize> {
+        let (columns, rows) = if self.is_compressed() {
+            (width.div_ceil(4).max(1), height.div_ceil(4).max(1))
+        } else {
+            (width, height)
+        };
+        columns.checked_mul(rows)?.checked_mul(self.unit_size())
+    }
+}
+
+/// A decoded texture as tightly packed RGBA8
+pub struct DecodedTexture {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ])
+}
+
+fn parse_format(data: &[u8]) -> Result<(Format, usize), String> {
+    let flags = read_u32(data, 80);
+    let four_cc = &data[84..88];
+    if flags & DDPF_FOURCC != 0 {
+        return match four_cc {
+            b"DXT1" => Ok((Format::Bc1, HEADER_END)),
+            b"DXT5" => Ok((Format::Bc3, HEADER_END)),
+            b"DX10" if data.len() >= DX10_HEADER_END => {
+                let dxgi = read_u32(data, HEADER_END);
+                Format::from_dxgi(dxgi)
+                    .map(|f| (f, DX10_HEADER_END))
+                    .ok_or_else(|| format!("Unsupported DXGI format {}", dxgi))
+            }
+            other => Err(format!(
+                "Unsupported DDS format {}",
+                String::from_utf8_lossy(other)
+            )),
+        };
+    }
+    if flags & DDPF_RGB != 0 && read_u32(data, 88) == 32 {
+        return match read_u32(data, 92) {
+            0x0000_00ff => Ok((Format::Rgba8, HEADER_END)),
+            0x00ff_0000 => Ok((Format::Bgra8, HEADER_END)),
+            _ => Err("Unsupported DDS channel layout".to_string()),
+        };
+    }
+    Err("Unsupported DDS pixel format".to_string())
+}
+
+/// Decode a BC1, BC3, BC7 or 8-bit RGBA texture at the first mip level whose longer side
+/// is still at least `min_size`
+pub fn decode_dds(data: &[u8], min_size: u32) -> Result<DecodedTexture, String> {
+    if data.len() < HEADER_END || &data[..4] != DDS_MAGIC {
+        return Err("Not a DDS file".to_string());
+    }
+    let mut height = read_u32(data, 12) as usize;
+    let mut width = read_u32(data, 16) as usize;
+    let mip_count = read_u32(data, 28).max(1);
+    let (format, mut offset) = parse_format(data)?;
+    if width == 0 || height == 0 {
+        return Err("DDS has no pixels".to_string());
+    }
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        return Err(format!("DDS is too large: {}x{}", width, height));
+    }
+    let truncated = || "DDS data is truncated".to_string();
+    let mut level = 1;
+    while level < mip_count && width.max(height) / 2 >= min_size as usize {
+        offset = format
+            .level_size(width, height)
+            .and_then(|size| offset.checked_add(size))
+            .ok_or_else(truncated)?;
+        width = (width / 2).max(1);
+        height = (height / 2).max(1);
+        level += 1;
+    }
+    // The level must be there before its pixels are allocated
+    let body = format
+        .level_size(width, height)
+        .and_then(|size| data.get(offset..offset.checked_add(size)?))
+        .ok_or_else(truncated)?;
+
+    let mut pixels = vec![0u8; width * height * 4];
+    if format.is_compressed() {
+        let blocks_x = width.div_ceil(4);
+        for (i, block) in body.chunks_exact(format.unit_size()).enumerate() {
+            let decoded = match format {
+                Format::Bc1 => decode_bc1(block, true),
+                Format::Bc3 => decode_bc3(block),
+                _ => decode_bc7(block),
+            };
+            let (bx, by) = ((i % blocks_x) * 4, (i / blocks_x) * 4);
+            for (p, rgba) in decoded.iter().enumerate() {
+                let (x, y) = (bx + p % 4, by + p / 4);
+                if x < width && y < height {
+                    let at = (y * width + x) * 4;
+                    pixels[at..at + 4].copy_from_slice(rgba);
+                }
+            }
+        }
+    } else {
+        pixels.copy_from_slice(body);
+        if format == Format::Bgra8 {
+            for pixel in pixels.chunks_exact_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+    }
+    Ok(DecodedTexture {
+        width: width as u32,
+        height: height as u32,
+        pixels,
+    })
+}
+
+fn rgb565(color: u16) -> [u8; 4] {
+    let r = ((color >> 11) & 0x1f) as u8;
+    let g = ((color >> 5) & 0x3f) as u8;
+    let b = (color & 0x1f) as u8;
+    [
+        (r << 3) | (r >> 2),
+        (g << 2) | (g >> 4),
+        (b << 3) | (b >> 2),
+        255,
+    ]
+}
+
+fn mix(a: u8, b: u8, wa: u32, wb: u32, div: u32) -> u8 {
+    ((a as u32 * wa + b as u32 * wb) / div) as u8
+}
+
+/// `punch_through` allows the 3-color mode with transparent black; BC2/BC3 color blocks never use it
+fn decode_bc1(block: &[u8], punch_through: bool) -> [[u8; 4]; 16] {
+    let c0 = u16::from_le_bytes([block[0], block[1]]);
+    let c1 = u16::from_le_bytes([block[2], block[3]]);
+    let (a, b) = (rgb565(c0), rgb565(c1));
+    let mut palette = [a, b, [0; 4], [0; 4]];
+    if c0 > c1 || !punch_through {
+        for c in 0..3 {
+            palette[2][c] = mix(a[c], b[c], 2, 1, 3);
+            palette[3][c] = mix(a[c], b[c], 1, 2, 3);
+        }
+        palette[2][3] = 255;
+        palette[3][3] = 255;
+    } else {
+        for c in 0..3 {
+            palette[2][c] = mix(a[c], b[c], 1, 1, 2);
+        }
+        palette[2][3] = 255;
+    }
+    let indices = read_u32(block, 4);
+    let mut out = [[0u8; 4]; 16];
+    for (i, pixel) in out.iter_mut().enumerate() {
+        *pixel = palette[((indices >> (i * 2)) & 3) as usize];
+    }
+    out
+}
+
+fn decode_bc3(block: &[u8]) -> [[u8; 4]; 16] {
+    let mut out = decode_bc1(&block[8..], false);
+    let (a0, a1) = (block[0], block[1]);
+    let mut alphas = [a0, a1, 0, 0, 0, 0, 0, 255];
+    if a0 > a1 {
+        for i in 1..7u32 {
+            alphas[i as usize + 1] = mix(a0, a1, 7 - i, i, 7);
+        }
+    } else {
+        for i in 1..5u32 {
+            alphas[i as usize + 1] = mix(a0, a1, 5 - i, i, 5);
+        }
+    }
+    let bits = block[2..8]
+        .iter()
+        .rev()
+        .fold(0u64, |acc, b| (acc << 8) | *b as u64);
+    for (i, pixel) in out.iter_mut().enumerate() {
+        pixel[3] = alphas[((bits >> (i * 3)) & 7) as usize];
+    }
+    out
+}
+
+/// Bit layout of one of the eight BC7 block modes
+struct Bc7Mode {
+    subsets: usize,
+    partition_bits: u32,
+    rotation_bits: u32,
+    selection_bits: u32,
+    color_bits: u32,
+    alpha_bits: u32,
+    endpoint_pbits: bool,
+    shared_pbits: bool,
+    index_bits: u32,
+    index2_bits: u32,
+}
+
+/// Build a mode from the columns of the BC7 mode table, in field order
+const fn bc7_mode(m: [u32; 10]) -> Bc7Mode {
+    Bc7Mode {
+        subsets: m[0] as usize,
+        partition_bits: m[1],
+        rotation_bits: m[2],
+        selection_bits: m[3],
+        color_bits: m[4],
+        alpha_bits: m[5],
+        endpoint_pbits: m[6] == 1,
+        shared_pbits: m[7] == 1,
+        index_bits: m[8],
+        index2_bits: m[9],
+    }
+}
+
+const BC7_MODES: [Bc7Mode; 8] = [
+    bc7_mode([3, 4, 0, 0, 4, 0, 1, 0, 3, 0]),
+    bc7_mode([2, 6, 0, 0, 6, 0, 0, 1, 3, 0]),
+    bc7_mode([3, 6, 0, 0, 5, 0, 0, 0, 2, 0]),
+    bc7_mode([2, 6, 0, 0, 7, 0, 1, 0, 2, 0]),
+    bc7_mode([1, 0, 2, 1, 5, 6, 0, 0, 2, 3]),
+    bc7_mode([1, 0, 2, 0, 7, 8, 0, 0, 2, 2]),
+    bc7_mode([1, 0, 0, 0, 7, 7, 1, 0, 4, 0]),
+    bc7_mode([2, 6, 0, 0, 5, 5, 1, 0, 2, 0]),
+];
+
+/// Two-subset partitions: bit `i` set means pixel `i` belongs to the second subset
+const BC7_PARTITIONS2: [u16; 64] = [
+    0xCCCC, 0x8888, 0xEEEE, 0xECC8, 0xC880, 0xFEEC, 0xFEC8, 0xEC80, 0xC800, 0xFFEC, 0xFE80, 0xE800,
+    0xFFE8, 0xFF00, 0xFFF0, 0xF000, 0xF710, 0x008E, 0x7100, 0x08CE, 0x008C, 0x7310, 0x3100, 0x8CCE,
+    0x088C, 0x3110, 0x6666, 0x366C, 0x17E8, 0x0FF0, 0x718E, 0x399C, 0xAAAA, 0xF0F0, 0x5A5A, 0x33CC,
+    0x3C3C, 0x55AA, 0x9696, 0xA55A, 0x73CE, 0x13C8, 0x324C, 0x3BDC, 0x6996, 0xC33C, 0x9966, 0x0660,
+    0x0272, 0x04E4, 0x4E40, 0x2720, 0xC936, 0x936C, 0x39C6, 0x639C, 0x9336, 0x9CC6, 0x817E, 0xE718,
+    0xCCF0, 0x0FCC, 0x7744, 0xEE22,
+];
+
+/// Three-subset partitions, subset of each pixel in row order
+const BC7_PARTITIONS3: [[u8; 16]; 64] = [
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 1, 2, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 2, 0, 0, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 1, 0, 1, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1, 1, 1, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1],
+    [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2],
+    [0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2, 0, 1, 1, 2],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0, 2, 2, 2, 0],
+    [0, 0, 0, 1, 0, 0, 1, 1, 0, 1, 1, 2, 1, 1, 2, 2],
+    [0, 1, 1, 1, 0, 0, 1, 1, 2, 0, 0, 1, 2, 2, 0, 0],
+    [0, 0, 0, 0, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 2, 2, 0, 0, 2, 2, 1, 1, 1, 1],
+    [0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2, 0, 2, 2, 2],
+    [0, 0, 0, 1, 0, 0, 0, 1, 2, 2, 2, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2],
+    [0, 0, 0, 0, 1, 1, 0, 0, 2, 2, 1, 0, 2, 2, 1, 0],
+    [0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1, 0, 0, 0, 0],
+    [0, 0, 1, 2, 0, 0, 1, 2, 1, 1, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1, 0, 1, 1, 0],
+    [0, 0, 0, 0, 0, 1, 1, 0, 1, 2, 2, 1, 1, 2, 2, 1],
+    [0, 0, 2, 2, 1, 1, 0, 2, 1, 1, 0, 2, 0, 0, 2, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 0, 0, 2, 2, 2, 2, 2],
+    [0, 0, 1, 1, 0, 1, 2, 2, 0, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 0, 0, 2, 0, 0, 0, 2, 2, 1, 1, 2, 2, 2, 1],
+    [0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 2, 2, 2],
+    [0, 2, 2, 2, 0, 0, 2, 2, 0, 0, 1, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 0, 0, 1, 2, 0, 0, 2, 2, 0, 2, 2, 2],
+    [0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0, 0, 1, 2, 0],
+    [0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0],
+    [0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0, 1, 2, 0],
+    [0, 1, 2, 0, 2, 0, 1, 2, 1, 2, 0, 1, 0, 1, 2, 0],
+    [0, 0, 1, 1, 2, 2, 0, 0, 1, 1, 2, 2, 0, 0, 1, 1],
+    [0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0, 1, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 0, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2, 1, 1, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 2, 2, 0, 0, 1, 1],
+    [0, 2, 2, 0, 1, 2, 2, 1, 0, 2, 2, 0, 1, 2, 2, 1],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 0, 1, 0, 1],
+    [0, 0, 0, 0, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1, 2, 1],
+    [0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 0, 1, 2, 2, 2, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 2, 2, 2, 0, 1, 1, 1],
+    [0, 0, 0, 2, 1, 1, 1, 2, 0, 0, 0, 2, 1, 1, 1, 2],
+    [0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 2, 2, 2, 0, 1, 1, 1, 0, 1, 1, 1, 0, 2, 2, 2],
+    [0, 0, 0, 2, 1, 1, 1, 2, 1, 1, 1, 2, 0, 0, 0, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2, 2, 1, 1, 2],
+    [0, 1, 1, 0, 0, 1, 1, 0, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 0, 2, 2, 0, 0, 1, 1, 0, 0, 1, 1, 0, 0, 2, 2],
+    [0, 0, 2, 2, 1, 1, 2, 2, 1, 1, 2, 2, 0, 0, 2, 2],
+    [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 1, 1, 2],
+    [0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 2, 0, 0, 0, 1],
+    [0, 2, 2, 2, 1, 2, 2, 2, 0, 2, 2, 2, 1, 2, 2, 2],
+    [0, 1, 0, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2],
+    [0, 1, 1, 1, 2, 0, 1, 1, 2, 2, 0, 1, 2, 2, 2, 0],
+];
+
+/// Anchor pixel of the second subset in two-subset partitions
+const BC7_ANCHORS2: [u8; 64] = [
+    15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 15, 2, 8, 2, 2, 8, 8, 15, 2, 8,
+    2, 2, 8, 8, 2, 2, 15, 15, 6, 8, 2, 8, 15, 15, 2, 8, 2, 2, 2, 15, 15, 6, 6, 2, 6, 8, 15, 15, 2,
+    2, 15, 15, 15, 15, 15, 2, 2, 15,
+];
+
+/// Anchor pixels of the second and third subsets in three-subset partitions
+const BC7_ANCHORS3: [[u8; 64]; 2] = [
+    [
+        3, 3, 15, 15, 8, 3, 15, 15, 8, 8, 6, 6, 6, 5, 3, 3, 3, 3, 8, 15, 3, 3, 6, 10, 5, 8, 8, 6,
+        8, 5, 15, 15, 8, 15, 3, 5, 6, 10, 8, 15, 15, 3, 15, 5, 15, 15, 15, 15, 3, 15, 5, 5, 5, 8,
+        5, 10, 5, 10, 8, 13, 15, 12, 3, 3,
+    ],
+    [
+        15, 8, 8, 3, 15, 15, 3, 8, 15, 15, 15, 15, 15, 15, 15, 8, 15, 8, 15, 3, 15, 8, 15, 8, 3,
+        15, 6, 10, 15, 15, 10, 8, 15, 3, 15, 10, 10, 8, 9, 10, 6, 15, 8, 15, 3, 6, 6, 8, 15, 3, 15,
+        15, 15, 15, 15, 15, 15, 15, 15, 15, 3, 15, 15, 8,
+    ],
+];
+
+const BC7_WEIGHTS2: [u32; 4] = [0, 21, 43, 64];
+const BC7_WEIGHTS3: [u32; 8] = [0, 9, 18, 27, 37, 46, 55, 64];
+const BC7_WEIGHTS4: [u32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+struct BitReader {
+    bits: u128,
+    pos: u32,
+}
+
+impl BitReader {
+    fn read(&mut self, count: u32) -> u32 {
+        if count == 0 {
+            return 0;
+        }
+        let value = (self.bits >> self.pos) & ((1u128 << count) - 1);
+        self.pos += count;
+        value as u32
+    }
+}
+
+fn bc7_weight(bits: u32, index: u32) -> u32 {
+    match bits {
+        2 => BC7_WEIGHTS2[index as usize],
+        3 => BC7_WEIGHTS3[index as usize],
+        _ => BC7_WEIGHTS4[index as usize],
+    }
+}
+
+fn bc7_interpolate(e0: u8, e1: u8, weight: u32) -> u8 {
+    (((64 - weight) * e0 as u32 + weight * e1 as u32 + 32) >> 6) as u8
+}
+
+/// Widen an endpoint of `bits` bits to 8 by repeating its high bits
+fn bc7_expand(value: u32, bits: u32) -> u8 {
+    let value = value << (8 - bits);
+    (value | (value >> bits)) as u8
+}
+
+fn decode_bc7(block: &[u8]) -> [[u8; 4]; 16] {
+    let mut out = [[0u8; 4]; 16];
+    let mut reader = BitReader {
+        bits: u128::from_le_bytes(block.try_into().unwrap_or([0; 16])),
+        pos: 0,
+    };
+    let mode_index = (reader.bits as u8).trailing_zeros();
+    let Some(mode) = BC7_MODES.get(mode_index as usize) else {
+        // Reserved mode: the format decodes it as transparent black
+        return out;
+    };
+    reader.pos = mode_index + 1;
+    let partition = reader.read(mode.partition_bits) as usize;
+    let rotation = reader.read(mode.rotation_bits);
+    let selection = reader.read(mode.selection_bits);
+
+    // endpoints[subset * 2 + end][channel]
+    let count = mode.subsets * 2;
+    let mut endpoints = [[0u32; 4]; 6];
+    for channel in 0..3 {
+        for endpoint in endpoints.iter_mut().take(count) {
+            endpoint[channel] = reader.read(mode.color_bits);
+        }
+    }
+    for endpoint in endpoints.iter_mut().take(count) {
+        endpoint[3] = reader.read(mode.alpha_bits);
+    }
+    let mut color_bits = mode.color_bits;
+    let mut alpha_bits = mode.alpha_bits;
+    if mode.endpoint_pbits || mode.shared_pbits {
+        let pbits: Vec<u32> = if mode.endpoint_pbits {
+            (0..count).map(|_| reader.read(1)).collect()
+        } else {
+            (0..mode.subsets)
+                .flat_map(|_| {
+                    let bit = reader.read(1);
+                    [bit, bit]
+                })
+                .collect()
+        };
+        for (endpoint, pbit) in endpoints.iter_mut().zip(pbits) {
+            for value in endpoint.iter_mut() {
+                *value = (*value << 1) | pbit;
+            }
+        }
+        color_bits += 1;
+        if alpha_bits > 0 {
+            alpha_bits += 1;
+        }
+    }
+    let mut colors = [[0u8; 4]; 6];
+    for (color, endpoint) in colors.iter_mut().zip(endpoints.iter()).take(count) {
+        for channel in 0..3 {
+            color[channel] = bc7_expand(endpoint[channel], color_bits);
+        }
+        color[3] = if alpha_bits > 0 {
+            bc7_expand(endpoint[3], alpha_bits)
+        } else {
+            255
+        };
+    }
+
+    let subset_of = |pixel: usize| -> usize {
+        match mode.subsets {
+            2 => ((BC7_PARTITIONS2[partition] >> pixel) & 1) as usize,
+            3 => BC7_PARTITIONS3[partition][pixel] as usize,
+            _ => 0,
+        }
+    };
+    let is_anchor = |pixel: usize| -> bool {
+        pixel == 0
+            || match mode.subsets {
+                2 => BC7_ANCHORS2[partition] as usize == pixel,
+                3 => {
+                    BC7_ANCHORS3[0][partition] as usize == pixel
+                        || BC7_ANCHORS3[1][partition] as usize == pixel
+                }
+                _ => false,
+            }
+    };
+    // Anchor pixels store their index with the top bit dropped
+    let mut indices = [0u32; 16];
+    for (pixel, index) in indices.iter_mut().enumerate() {
+        *index = reader.read(mode.index_bits - is_anchor(pixel) as u32);
+    }
+    let mut indices2 = [0u32; 16];
+    if mode.index2_bits > 0 {
+        for (pixel, index) in indices2.iter_mut().enumerate() {
+            *index = reader.read(mode.index2_bits - (pixel == 0) as u32);
+        }
+    }
+
+    for (pixel, rgba) in out.iter_mut().enumerate() {
+        let subset = subset_of(pixel);
+        let (e0, e1) = (colors[subset * 2], colors[subset * 2 + 1]);
+        let (color_weight, alpha_weight) = if mode.index2_bits == 0 {
+            let w = bc7_weight(mode.index_bits, indices[pixel]);
+            (w, w)
+        } else if selection == 0 {
+            (
+                bc7_weight(mode.index_bits, indices[pixel]),
+                bc7_weight(mode.index2_bits, indices2[pixel]),
+            )
+        } else {
+            (
+                bc7_weight(mode.index2_bits, indices2[pixel]),
+                bc7_weight(mode.index_bits, indices[pixel]),
+            )
+        };
+        for channel in 0..3 {
+            rgba[channel] = bc7_interpolate(e0[channel], e1[channel], color_weight);
+        }
+        rgba[3] = bc7_interpolate(e0[3], e1[3], alpha_weight);
+        match rotation {
+            1 => rgba.swap(0, 3),
+            2 => rgba.swap(1, 3),
+            3 => rgba.swap(2, 3),
+            _ => {}
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pack `(value, bits)` fields into a block, lowest bit first
+    fn pack(fields: &[(u32, u32)]) -> [u8; 16] {
+        let mut bits = 0u128;
+        let mut pos = 0;
+        for (value, count) in fields {
+            bits |= (*value as u128) << pos;
+            pos += count;
+        }
+        assert_eq!(pos, 128);
+        bits.to_le_bytes()
+    }
+
+    fn dds(width: u32, height: u32, four_cc: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut data = vec![0u8; HEADER_END];
+        data[..4].copy_from_slice(DDS_MAGIC);
+        data[12..16].copy_from_slice(&height.to_le_bytes());
+        data[16..20].copy_from_slice(&width.to_le_bytes());
+        data[80..84].copy_from_slice(&DDPF_FOURCC.to_le_bytes());
+        data[84..88].copy_from_slice(four_cc);
+        data.extend_from_slice(body);
+        data
+    }
+
+    #[test]
+    fn bc1_four_color_block() {
+        // Red and blue endpoints, pixels 0-3 use indices 0-3
+        let block = [0x00, 0xF8, 0x1F, 0x00, 0xE4, 0x00, 0x00, 0x00];
+        let out = decode_bc1(&block, true);
+        assert_eq!(out[0], [255, 0, 0, 255]);
+        assert_eq!(out[1], [0, 0, 255, 255]);
+        assert_eq!(out[2], [170, 0, 85, 255]);
+        assert_eq!(out[3], [85, 0, 170, 255]);
+        assert_eq!(out[15], [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn bc1_punch_through_block() {
+        // c0 <= c1 selects the three-color mode with transparent black
+        let block = [0x1F, 0x00, 0x00, 0xF8, 0xE4, 0x00, 0x00, 0x00];
+        let out = decode_bc1(&block, true);
+        assert_eq!(out[2], [127, 0, 127, 255]);
+        assert_eq!(out[3], [0, 0, 0, 0]);
+        // BC3 color blocks always use four colors
+        assert_eq!(decode_bc1(&block, false)[3], [170, 0, 85, 255]);
+    }
+
+    #[test]
+    fn bc3_alpha_block() {
+        // Alpha 255 to 0 with pixels 0-2 at indices 0-2, white color
+        let mut block = [0u8; 16];
+        block[0] = 255;
+        block[2] = 0x88;
+        block[8..10].copy_from_slice(&[0xFF, 0xFF]);
+        let out = decode_bc3(&block);
+        assert_eq!(out[0], [255, 255, 255, 255]);
+        assert_eq!(out[1][3], 0);
+        assert_eq!(out[2][3], 218);
+
+        // a0 <= a1 has six steps plus fixed 0 and 255
+        let mut block = [0u8; 16];
+        block[1] = 255;
+        // Pixels 0-2 at indices 6, 7 and 1
+        block[2] = 0b0111_1110;
+        let out = decode_bc3(&block);
+        assert_eq!(out[0][3], 0);
+        assert_eq!(out[1][3], 255);
+        assert_eq!(out[2][3], 255);
+    }
+
+    #[test]
+    fn bc7_mode6_block() {
+        // Mode 6: red and blue 7-bit endpoints with p-bits 1 and 0
+        let mut fields = vec![(0b100_0000, 7)];
+        fields.extend([(0x7F, 7), (0, 7), (0, 7), (0, 7), (0, 7), (0x7F, 7)]);
+        fields.extend([(0x7F, 7), (0x7F, 7), (1, 1), (0, 1)]);
+        // Pixel 0 (anchor, 3 bits) at e0, pixel 1 at e1, pixel 2 halfway
+        fields.extend([(0, 3), (15, 4), (8, 4)]);
+        fields.extend(std::iter::repeat_n((0, 4), 13));
+        let out = decode_bc7(&pack(&fields));
+        assert_eq!(out[0], [255, 1, 1, 255]);
+        assert_eq!(out[1], [0, 0, 254, 254]);
+        assert_eq!(out[2], [120, 0, 135, 254]);
+        assert_eq!(out[15], [255, 1, 1, 255]);
+    }
+
+    #[test]
+    fn bc7_reserved_mode_is_transparent_black() {
+        assert_eq!(decode_bc7(&[0u8; 16]), [[0u8; 4]; 16]);
+    }
+
+    #[test]
+    fn decodes_small_texture() {
+        let block = [0x00, 0xF8, 0x1F, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let texture = decode_dds(&dds(2, 2, b"DXT1", &block), 1).unwrap();
+        assert_eq!((texture.width, texture.height), (2, 2));
+        assert_eq!(texture.pixels, [255, 0, 0, 255].repeat(4));
+    }
+
+    #[test]
+    fn rejects_truncated_and_oversized_textures() {
+        assert!(decode_dds(&dds(8, 8, b"DXT1", &[0; 16]), 1).is_err());
+        assert!(decode_dds(&dds(u32::MAX, u32::MAX, b"DXT5", &[0; 16]), 1).is_err());
+        assert!(decode_dds(&dds(16385, 4, b"DXT1", &[0; 16]), 1).is_err());
+        assert!(decode_dds(&[0; 64], 1).is_err());
+    }
+}
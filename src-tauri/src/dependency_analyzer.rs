@@ -0,0 +1,235 @@
+use crate::app_config::app_path;
+use crate::ini_parser::load_mod_ini;
+use crate::mod_manager::{active_inis, relative_path, scan_mods};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// User map of namespaces to the mod that provides them, `{ "<namespace>": NamespaceProvider }`
+const PROVIDERS_FILE: &str = "namespace_providers.json";
+
+/// Section prefixes that can be referenced across namespaces as `<Prefix>\<namespace>\<name>`
+const REFERENCE_PREFIXES: &[&str] = &["CommandList", "Resource", "CustomShader"];
+
+/// Namespaces commonly called into, and what provides them
+const KNOWN_PROVIDERS: &[(&str, &str, bool)] = &[
+    ("global\\ORFix", "ORFix", false),
+    ("global\\NNFix", "NNFix", false),
+    ("WWMIv1", "WWMI", true),
+    ("ZZMI", "ZZMI", true),
+];
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct NamespaceProvider {
+    pub name: String,
+    /// Where to get it, usually a GameBanana page
+    pub source: String,
+    /// Shipped with the model importer itself rather than installed as a mod
+    pub builtin: bool,
+}
+
+/// A cross-namespace reference no enabled mod satisfies
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingDependency {
+    pub mod_path: String,
+    pub mod_name: String,
+    pub ini: String,
+    pub section: String,
+    pub line: usize,
+    pub target: String,
+    pub namespace: String,
+    /// True when the namespace exists but lacks the referenced section
+    pub section_missing: bool,
+    pub provider: Option<NamespaceProvider>,
+    /// Installed but disabled mods that would provide the namespace
+    pub disabled_providers: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DependencyReport {
+    pub mods_scanned: usize,
+    /// Namespaces declared by enabled mods, with the mods declaring them
+    pub namespaces: BTreeMap<String, Vec<String>>,
+    pub missing: Vec<MissingDependency>,
+}
+
+/// Sections one namespace defines, gathered from every ini declaring it
+#[derive(Default)]
+struct NamespaceInfo {
+    sections: BTreeSet<String>,
+    enabled_mods: BTreeSet<String>,
+    disabled_mods: BTreeSet<String>,
+}
+
+/// A reference found in an ini, before it is resolved
+struct Reference {
+    mod_path: String,
+    mod_name: String,
+    ini: String,
+    section: String,
+    line: usize,
+    target: String,
+    namespace: String,
+    section_name: String,
+}
+
+fn load_providers() -> BTreeMap<String, NamespaceProvider> {
+    let mut providers: BTreeMap<String, NamespaceProvider> = KNOWN_PROVIDERS
+        .iter()
+        .map(|(namespace, name, builtin)| {
+            (
+                namespace.to_ascii_lowercase(),
+                NamespaceProvider {
+                    name: name.to_string(),
+                    source: String::new(),
+                    builtin: *builtin,
+                },
+            )
+        })
+        .collect();
+    let extra: BTreeMap<String, NamespaceProvider> =
+        std::fs::read_to_string(app_path(PROVIDERS_FILE))
+            .ok()
+            .and_then(|data| serde_json::from_str(&data).ok())
+            .unwrap_or_default();
+    providers.extend(
+        extra
+            .into_iter()
+            .map(|(namespace, provider)| (namespace.to_ascii_lowercase(), provider)),
+    );
+    providers
+}
+
+/// Split `CommandList\global\ORFix\ORFix` into (`global\orfix`, `commandlistorfix`)
+fn parse_reference(token: &str) -> Option<(String, String)> {
+    let prefix = REFERENCE_PREFIXES.iter().find(|p| {
+        token.len() > p.len()
+            && token.is_char_boundary(p.len())
+            && token[..p.len()].eq_ignore_ascii_case(p)
+            && token[p.len()..].starts_with('\\')
+    })?;
+    let (namespace, name) = token[prefix.len() + 1..].rsplit_once('\\')?;
+    if namespace.is_empty() || name.is_empty() {
+        return None;
+    }
+    Some((
+        namespace.to_ascii_lowercase(),
+        format!("{}{}", prefix, name).to_ascii_lowercase(),
+    ))
+}
+
+/// Namespace an ini lives in: its declared one, or its path as 3DMigoto names it
+fn ini_namespace(declared: Option<&str>, game_root: &Path, ini: &Path) -> String {
+    match declared {
+        Some(namespace) => namespace.trim_matches('\\').to_ascii_lowercase(),
+        None => relative_path(game_root, ini)
+            .replace('/', "\\")
+            .to_ascii_lowercase(),
+    }
+}
+
+fn analyze(root: &Path) -> DependencyReport {
+    let mut report = DependencyReport::default();
+    let game_root = root.parent().unwrap_or(root);
+    let mut namespaces: BTreeMap<String, NamespaceInfo> = BTreeMap::new();
+    let mut references = Vec::new();
+
+    for installed in scan_mods(root) {
+        let mod_dir = PathBuf::from(&installed.path);
+        if installed.enabled {
+            report.mods_scanned += 1;
+        }
+        for ini in active_inis(&mod_dir) {
+            let model = match load_mod_ini(&ini) {
+                Ok(model) => model,
+                Err(e) => {
+                    println!("{}", e);
+                    continue;
+                }
+            };
+            let namespace = ini_namespace(model.namespace.as_deref(), game_root, &ini);
+            let info = namespaces.entry(namespace).or_default();
+            info.sections
+                .extend(model.sections.iter().map(|s| s.name.to_ascii_lowercase()));
+            if !installed.enabled {
+                info.disabled_mods.insert(installed.name.clone());
+                continue;
+            }
+            info.enabled_mods.insert(installed.name.clone());
+            if let Some(declared) = &model.namespace {
+                report
+                    .namespaces
+                    .entry(declared.clone())
+                    .or_default()
+                    .push(installed.name.clone());
+            }
+            for section in &model.sections {
+                for entry in &section.entries {
+                    for token in entry.value.split_whitespace() {
+                        let Some((namespace, section_name)) = parse_reference(token) else {
+                            continue;
+                        };
+                        references.push(Reference {
+                            mod_path: installed.path.clone(),
+                            mod_name: installed.name.clone(),
+                            ini: relative_path(&mod_dir, &ini),
+                            section: section.name.clone(),
+                            line: entry.line,
+                            target: token.to_string(),
+                            namespace,
+                            section_name,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    let providers = load_providers();
+    for reference in references {
+        let info = namespaces.get(&reference.namespace);
+        let enabled = info.is_some_and(|i| !i.enabled_mods.is_empty());
+        let provider = providers.get(&reference.namespace).cloned();
+        if provider.as_ref().is_some_and(|p| p.builtin) && !enabled {
+            continue;
+        }
+        let section_missing =
+            enabled && info.is_some_and(|i| !i.sections.contains(&reference.section_name));
+        if enabled && !section_missing {
+            continue;
+        }
+        report.missing.push(MissingDependency {
+            mod_path: reference.mod_path,
+            mod_name: reference.mod_name,
+            ini: reference.ini,
+            section: reference.section,
+            line: reference.line,
+            target: reference.target,
+            namespace: reference.namespace,
+            section_missing,
+            provider,
+            disabled_providers: info
+                .map(|i| i.disabled_mods.iter().cloned().collect())
+                .unwrap_or_default(),
+        });
+    }
+    report
+}
+
+/// Resolve every cross-namespace `run`/resource reference of a game's enabled mods and report the unresolved ones
+#[tauri::command]
+pub fn find_missing_dependencies(game: String) -> Result<DependencyReport, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    let report = analyze(&root);
+    println!(
+        "Dependency scan for {}: {} mods, {} namespaces, {} unresolved references",
+        game,
+        report.mods_scanned,
+        report.namespaces.len(),
+        report.missing.len()
+    );
+    Ok(report)
+}
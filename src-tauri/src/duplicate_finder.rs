@@ -0,0 +1,158 @@
+use crate::fs_utils::{hash_bytes, hash_file, path_size, walk_files};
+use crate::manifest::ModManifest;
+use crate::mod_manager::{scan_mods, InstalledMod};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// Files that define what a mod does; previews, readmes and textures vary between repacks
+const FINGERPRINT_EXTS: &[&str] = &["ini", "buf", "ib", "vb"];
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateReason {
+    /// Installed from the same file of a GameBanana mod
+    Source,
+    /// Same ini and buffer contents
+    Content,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateMod {
+    pub path: String,
+    pub name: String,
+    pub category: String,
+    pub enabled: bool,
+    pub size: u64,
+    pub installed: u64,
+    pub fname: String,
+    /// Hash of the ini and buffer contents, see `content_fingerprint`
+    pub fingerprint: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    pub reason: DuplicateReason,
+    /// GameBanana mod and file, or content fingerprint shared by the group
+    pub key: String,
+    pub mods: Vec<DuplicateMod>,
+    /// The copy worth keeping: enabled first, then the most recently installed
+    pub keep: String,
+    /// Bytes freed by removing the copies whose contents match the one worth keeping
+    pub reclaimable: u64,
+}
+
+/// Hash of the ini and buffer contents of a mod, independent of file names
+fn content_fingerprint(mod_dir: &Path) -> Option<String> {
+    let mut hashes: Vec<String> = walk_files(mod_dir)
+        .into_iter()
+        .filter(|f| {
+            f.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| FINGERPRINT_EXTS.iter().any(|x| x.eq_ignore_ascii_case(e)))
+        })
+        .filter_map(|f| hash_file(&f).ok())
+        .collect();
+    if hashes.is_empty() {
+        return None;
+    }
+    hashes.sort();
+    Some(hash_bytes(hashes.join("\n").as_bytes()))
+}
+
+/// `<mod id>/<file>` of the GameBanana download; variants of one page are different files
+fn source_key(manifest: &ModManifest) -> Option<String> {
+    let mod_id = manifest.mod_id()?;
+    match manifest.file_id() {
+        Some(file_id) => Some(format!("{}/{}", mod_id, file_id)),
+        None if !manifest.fname.is_empty() => Some(format!("{}/{}", mod_id, manifest.fname)),
+        None => None,
+    }
+}
+
+fn duplicate_mod(installed: &InstalledMod) -> DuplicateMod {
+    let manifest = installed.manifest.clone().unwrap_or_default();
+    DuplicateMod {
+        path: installed.path.clone(),
+        name: installed.name.clone(),
+        category: installed.category_name(),
+        enabled: installed.enabled,
+        size: path_size(Path::new(&installed.path)),
+        installed: manifest.installed,
+        fname: manifest.fname,
+        fingerprint: content_fingerprint(Path::new(&installed.path)),
+    }
+}
+
+fn duplicate_group(
+    reason: DuplicateReason,
+    key: String,
+    mut mods: Vec<DuplicateMod>,
+) -> DuplicateGroup {
+    mods.sort_by_key(|m| (std::cmp::Reverse(m.enabled), std::cmp::Reverse(m.installed)));
+    let keep = mods[0].path.clone();
+    // Only identical contents are safe to remove; copies of one file may since have been edited
+    let reclaimable = match &mods[0].fingerprint {
+        Some(fingerprint) => mods
+            .iter()
+            .skip(1)
+            .filter(|m| m.fingerprint.as_ref() == Some(fingerprint))
+            .map(|m| m.size)
+            .sum(),
+        None => 0,
+    };
+    DuplicateGroup {
+        reason,
+        key,
+        mods,
+        keep,
+        reclaimable,
+    }
+}
+
+/// Group a game's installed mods that share a GameBanana file or identical ini/buffer contents
+#[tauri::command]
+pub fn find_duplicate_mods(game: String) -> Result<Vec<DuplicateGroup>, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    let mut by_source: BTreeMap<String, Vec<DuplicateMod>> = BTreeMap::new();
+    let mut by_content: BTreeMap<String, Vec<DuplicateMod>> = BTreeMap::new();
+    for installed in scan_mods(&root) {
+        let entry = duplicate_mod(&installed);
+        if let Some(key) = installed.manifest.as_ref().and_then(source_key) {
+            by_source.entry(key).or_default().push(entry.clone());
+        }
+        if let Some(fingerprint) = entry.fingerprint.clone() {
+            by_content.entry(fingerprint).or_default().push(entry);
+        }
+    }
+
+    let mut groups: Vec<DuplicateGroup> = by_source
+        .into_iter()
+        .filter(|(_, mods)| mods.len() > 1)
+        .map(|(key, mods)| duplicate_group(DuplicateReason::Source, key, mods))
+        .collect();
+    // Copies of one download show up under both reasons; report them once
+    let source_sets: Vec<BTreeSet<&String>> = groups
+        .iter()
+        .map(|g| g.mods.iter().map(|m| &m.path).collect())
+        .collect();
+    let content_groups: Vec<DuplicateGroup> = by_content
+        .into_iter()
+        .filter(|(_, mods)| mods.len() > 1)
+        .filter(|(_, mods)| {
+            let paths: BTreeSet<&String> = mods.iter().map(|m| &m.path).collect();
+            !source_sets.contains(&paths)
+        })
+        .map(|(fingerprint, mods)| duplicate_group(DuplicateReason::Content, fingerprint, mods))
+        .collect();
+    groups.extend(content_groups);
+    println!(
+        "Duplicate scan for {}: {} groups, {} reclaimable",
+        game,
+        groups.len(),
+        crate::format_bytes(groups.iter().map(|g| g.reclaimable).sum())
+    );
+    Ok(groups)
+}
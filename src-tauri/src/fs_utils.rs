@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+/// Files that may sit next to the single wrapper folder of an archive
+const WRAPPER_IGNORED_EXTS: &[&str] = &["txt", "md", "nfo", "png", "jpg", "jpeg", "webp", "gif"];
+
+/// Every file below `dir`, depth first
+pub fn walk_files(dir: &Path) -> Vec<PathBuf> {
+    walk(dir, true).0
+}
+
+/// Files below `dir`, and separately the links to folders in it, which are not followed
+pub fn walk_files_and_links(dir: &Path) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    walk(dir, false)
+}
+
+fn walk(dir: &Path, follow_links: bool) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let mut files = Vec::new();
+    let mut links = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !follow_links && crate::library_store::is_link(&path) {
+                links.push(path);
+            } else if path.is_dir() {
+                pending.push(path);
+            } else if path.is_file() {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    links.sort();
+    (files, links)
+}
+
+/// Total size in bytes of a file, or of every file below a folder
+pub fn path_size(path: &Path) -> u64 {
+    if path.is_file() {
+        return path.metadata().map(|m| m.len()).unwrap_or(0);
+    }
+    walk_files(path)
+        .iter()
+        .filter_map(|f| f.metadata().ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+fn fnv_update(mut hash: u64, bytes: &[u8]) -> u64 {
+    for byte in bytes {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+/// Stable 64-bit FNV-1a hash of some bytes, as hex
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    format!("{:016x}", fnv_update(FNV_OFFSET, bytes))
+}
+
+/// Stable 64-bit FNV-1a content hash of a file, as hex
+pub fn hash_file(path: &Path) -> std::io::Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut buffer = [0u8; 64 * 1024];
+    let mut hash = FNV_OFFSET;
+    loop {
+        let read = reader.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hash = fnv_update(hash, &buffer[..read]);
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+/// Hash every file below `dir`, keyed by its forward-slash relative path
+pub fn hash_tree(dir: &Path, skip: impl Fn(&str) -> bool) -> BTreeMap<String, String> {
+    walk_files(dir)
+        .into_iter()
+        .filter_map(|file| {
+            let rel = crate::mod_manager::relative_path(dir, &file);
+            if skip(&rel) {
+                return None;
+            }
+            hash_file(&file).ok().map(|hash| (rel, hash))
+        })
+        .collect()
+}
+
+/// Recursively copy a folder
+pub fn copy_dir_all(src: &Path, dest: &Path) -> Result<(), String> {
+    std::fs::create_dir_all(dest).map_err(|e| format!("Failed to create {:?}: {}", dest, e))?;
+    let entries = std::fs::read_dir(src).map_err(|e| format!("Failed to read {:?}: {}", src, e))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let path = entry.path();
+        let target = dest.join(entry.file_name());
+        if path.is_dir() {
+            copy_dir_all(&path, &target)?;
+        } else {
+            std::fs::copy(&path, &target)
+                .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", path, target, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Move a file or folder, copying when a plain rename is not possible (e.g. across drives)
+pub fn move_path(src: &Path, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {:?}: {}", parent, e))?;
+    }
+    if std::fs::rename(src, dest).is_ok() {
+        return Ok(());
+    }
+    if src.is_dir() {
+        copy_dir_all(src, dest)?;
+        std::fs::remove_dir_all(src).map_err(|e| format!("Failed to remove {:?}: {}", src, e))
+    } else {
+        std::fs::copy(src, dest)
+            .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", src, dest, e))?;
+        std::fs::remove_file(src).map_err(|e| format!("Failed to remove {:?}: {}", src, e))
+    }
+}
+
+/// Swap `replacement` in place of `target`, restoring the original on failure
+pub fn replace_dir(target: &Path, replacement: &Path) -> Result<(), String> {
+    let name = target
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {:?}", target))?;
+    let old = target.with_file_name(format!("{}.imi-old", name));
+    std::fs::rename(target, &old).map_err(|e| format!("Failed to move folder aside: {}", e))?;
+    if let Err(e) = move_path(replacement, target) {
+        let _ = std::fs::remove_dir_all(target);
+        let _ = std::fs::rename(&old, target);
+        return Err(e);
+    }
+    if let Err(e) = std::fs::remove_dir_all(&old) {
+        println!("Failed to remove previous copy {:?}: {}", old, e);
+    }
+    Ok(())
+}
+
+/// Descend through wrapper folders an archive was packed with, like `validateModDownload` does
+pub fn content_root(dir: &Path) -> PathBuf {
+    let mut root = dir.to_path_buf();
+    loop {
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            return root;
+        };
+        let entries: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+        let dirs: Vec<&PathBuf> = entries.iter().filter(|p| p.is_dir()).collect();
+        let loose_files = entries.iter().any(|p| {
+            let ext = p
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("")
+                .to_ascii_lowercase();
+            p.is_file() && !WRAPPER_IGNORED_EXTS.contains(&ext.as_str())
+        });
+        if dirs.len() != 1 || loose_files {
+            return root;
+        }
+        root = dirs[0].clone();
+    }
+}
@@ -0,0 +1,142 @@
+use crate::app_config::load_config;
+use crate::gamebanana::{fetch_bytes, fetch_mod, RemoteImage};
+use crate::manifest::{read_manifest, write_manifest, GalleryImage, ModManifest};
+use image::ImageFormat;
+use std::io::Cursor;
+use std::path::Path;
+
+/// The first gallery image is stored as `preview.<ext>`, the rest as `preview.<n>.<ext>`
+const PREVIEW_STEM: &str = "preview";
+
+/// Whether a file name is a mod's main preview, `preview.<ext>`
+pub fn is_main_preview(name: &str) -> bool {
+    name.to_ascii_lowercase()
+        .strip_prefix("preview.")
+        .is_some_and(|ext| !ext.is_empty() && !ext.contains('.'))
+}
+
+/// Keep JPEG and PNG as downloaded; other formats become PNG when they carry alpha, JPEG otherwise
+fn normalize_image(data: Vec<u8>) -> Result<(Vec<u8>, &'static str), String> {
+    let format = image::guess_format(&data).map_err(|e| format!("Not an image: {}", e))?;
+    match format {
+        ImageFormat::Jpeg => return Ok((data, "jpg")),
+        ImageFormat::Png => return Ok((data, "png")),
+        _ => {}
+    }
+    let decoded = image::load_from_memory_with_format(&data, format).map_err(|e| e.to_string())?;
+    let mut out = Cursor::new(Vec::new());
+    let ext = if decoded.color().has_alpha() {
+        decoded
+            .to_rgba8()
+            .write_to(&mut out, ImageFormat::Png)
+            .map_err(|e| e.to_string())?;
+        "png"
+    } else {
+        decoded
+            .to_rgb8()
+            .write_to(&mut out, ImageFormat::Jpeg)
+            .map_err(|e| e.to_string())?;
+        "jpg"
+    };
+    Ok((out.into_inner(), ext))
+}
+
+/// Gallery of the GameBanana page a mod was installed from
+async fn remote_gallery(manifest: &ModManifest) -> Result<Vec<RemoteImage>, String> {
+    let mod_id = manifest
+        .mod_id()
+        .ok_or_else(|| format!("{} has no GameBanana source", manifest.name))?;
+    let remote = fetch_mod(mod_id).await.map_err(|e| e.to_string())?;
+    Ok(remote.preview_media.images)
+}
+
+/// Remove the main preview and any gallery images saved earlier
+fn clear_gallery(mod_dir: &Path, manifest: &ModManifest) {
+    let mut stale: Vec<String> = manifest.gallery.iter().map(|g| g.file.clone()).collect();
+    if let Ok(entries) = std::fs::read_dir(mod_dir) {
+        stale.extend(
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|name| is_main_preview(name)),
+        );
+    }
+    for file in stale {
+        let path = mod_dir.join(&file);
+        if path.is_file() {
+            if let Err(e) = std::fs::remove_file(&path) {
+                println!("Failed to remove old preview {:?}: {}", path, e);
+            }
+        }
+    }
+}
+
+/// Images on the GameBanana page of an installed mod, for picking which ones to save
+#[tauri::command]
+pub async fn get_mod_gallery(path: String) -> Result<Vec<RemoteImage>, String> {
+    let manifest = read_manifest(Path::new(&path))
+        .ok_or_else(|| format!("No install manifest found in {}", path))?;
+    remote_gallery(&manifest).await
+}
+
+/// Save gallery images of a mod's GameBanana page into its folder and record them in the manifest.
+/// `images` picks gallery positions; by default the first image, or all with `previewGallery` set.
+#[tauri::command]
+pub async fn download_mod_gallery(
+    path: String,
+    images: Option<Vec<usize>>,
+) -> Result<Vec<GalleryImage>, String> {
+    let mod_dir = Path::new(&path);
+    let mut manifest =
+        read_manifest(mod_dir).ok_or_else(|| format!("No install manifest found in {}", path))?;
+    let gallery = remote_gallery(&manifest).await?;
+    if gallery.is_empty() {
+        println!("{} has no gallery images", path);
+        return Ok(Vec::new());
+    }
+    let mut wanted = images.unwrap_or_else(|| {
+        if load_config().preview_gallery.unwrap_or(false) {
+            (0..gallery.len()).collect()
+        } else {
+            vec![0]
+        }
+    });
+    let mut seen = std::collections::BTreeSet::new();
+    wanted.retain(|i| *i < gallery.len() && seen.insert(*i));
+
+    // Fetch everything before touching the folder, so a failed download keeps the old previews
+    let mut fetched = Vec::new();
+    for index in wanted {
+        let image = &gallery[index];
+        let url = image.url();
+        match fetch_bytes(&url).await.and_then(normalize_image) {
+            Ok((data, ext)) => fetched.push((image, url, data, ext)),
+            Err(e) => println!("Skipping gallery image {}: {}", url, e),
+        }
+    }
+    if fetched.is_empty() {
+        return Err(format!("Failed to download any gallery image for {}", path));
+    }
+
+    clear_gallery(mod_dir, &manifest);
+    let mut saved = Vec::new();
+    for (n, (image, url, data, ext)) in fetched.into_iter().enumerate() {
+        let file = if n == 0 {
+            format!("{}.{}", PREVIEW_STEM, ext)
+        } else {
+            format!("{}.{}.{}", PREVIEW_STEM, n + 1, ext)
+        };
+        std::fs::write(mod_dir.join(&file), data)
+            .map_err(|e| format!("Failed to write {}: {}", file, e))?;
+        saved.push(GalleryImage {
+            file,
+            source: url,
+            caption: image.caption.clone(),
+        });
+    }
+    manifest.preview = saved[0].source.clone();
+    manifest.gallery = saved.clone();
+    write_manifest(mod_dir, &manifest)?;
+    println!("Saved {} gallery images for {}", saved.len(), path);
+    Ok(saved)
+}
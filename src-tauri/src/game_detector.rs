@@ -0,0 +1,320 @@
+use crate::app_config::{app_path, load_config};
+use crate::fs_utils::walk_files;
+use crate::ini_parser::{parse_ini_bytes, SectionKind};
+use crate::manifest::now_secs;
+use crate::mod_manager::{is_ini, relative_path};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use tauri_plugin_shell::ShellExt;
+
+/// Extra per-game signatures, `{ "<game>": { "<token>": <weight> } }`, merged over the built-in ones
+const SIGNATURES_FILE: &str = "game_signatures.json";
+/// Shader and texture hashes per game: `{ "<game>": ["<hash>", ...] }`, merged over the learned ones
+const KNOWN_HASHES_FILE: &str = "game_hashes.json";
+/// Hashes learned from the mods already in each game's Mods folder
+const HASH_INDEX_FILE: &str = "game_hash_index.json";
+/// The learned index is rebuilt once it is older than this
+const HASH_INDEX_MAX_AGE: u64 = 24 * 60 * 60;
+/// Weight of one shader or texture hash known to belong to a game
+const HASH_WEIGHT: u32 = 2;
+/// A game is only suggested once its score reaches this
+const MIN_SCORE: u32 = 3;
+
+/// Lower-case tokens found in ini files (comments included) and how strongly they point at a game.
+/// Importer namespaces and tool banners are decisive, importer-only syntax and shared fixes less so.
+const BUILTIN_SIGNATURES: &[(&str, &[(&str, u32)])] = &[
+    (
+        "WW",
+        &[
+            ("wwmi", 5),
+            ("override_vertex_count", 3),
+            ("override_byte_stride", 3),
+            ("textureoverridecomponent", 1),
+        ],
+    ),
+    ("ZZ", &[("zzmi", 5), ("commandlistskintexture", 2)]),
+    ("GI", &[("gimi", 5), ("orfix", 3), ("nnfix", 2)]),
+    ("SR", &[("srmi", 5)]),
+    ("EF", &[("efmi", 5)]),
+];
+
+/// A signature token found in one of the mod's ini files
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GameEvidence {
+    pub game: String,
+    pub token: String,
+    pub ini: String,
+    pub weight: u32,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GameCheck {
+    pub expected: String,
+    /// Best scoring game, if any reached the threshold
+    pub detected: Option<String>,
+    /// False only when another game clearly matches better than the expected one
+    pub matches: bool,
+    pub scores: BTreeMap<String, u32>,
+    pub evidence: Vec<GameEvidence>,
+    pub ini_count: usize,
+    pub warning: Option<String>,
+}
+
+type Signatures = BTreeMap<String, BTreeMap<String, u32>>;
+type KnownHashes = BTreeMap<String, BTreeSet<String>>;
+
+#[derive(Serialize, Deserialize, Default)]
+struct HashIndex {
+    built: u64,
+    hashes: KnownHashes,
+}
+
+fn load_signatures() -> Signatures {
+    let mut signatures: Signatures = BUILTIN_SIGNATURES
+        .iter()
+        .map(|(game, tokens)| {
+            (
+                game.to_string(),
+                tokens.iter().map(|(t, w)| (t.to_string(), *w)).collect(),
+            )
+        })
+        .collect();
+    let extra: Signatures = std::fs::read_to_string(app_path(SIGNATURES_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    for (game, tokens) in extra {
+        let entry = signatures.entry(game).or_default();
+        for (token, weight) in tokens {
+            entry.insert(token.to_ascii_lowercase(), weight);
+        }
+    }
+    signatures
+}
+
+/// `hash =` values of the shader and texture overrides in an ini
+fn override_hashes(bytes: &[u8]) -> BTreeSet<String> {
+    parse_ini_bytes(bytes)
+        .sections
+        .iter()
+        .filter(|s| {
+            matches!(
+                s.kind,
+                SectionKind::ShaderOverride | SectionKind::TextureOverride
+            )
+        })
+        .flat_map(|s| s.get_all("hash"))
+        .map(|h| h.trim().trim_start_matches("0x").to_ascii_lowercase())
+        .filter(|h| h.len() == 8 || h.len() == 16)
+        .filter(|h| h.chars().all(|c| c.is_ascii_hexdigit()))
+        .collect()
+}
+
+/// Collect the override hashes of every ini in each configured Mods folder.
+/// Hashes seen under more than one game are dropped, they tell nothing apart.
+fn build_hash_index() -> HashIndex {
+    let mut hashes = KnownHashes::new();
+    for (game, root) in load_config().paths {
+        if game.is_empty() || root.is_empty() {
+            continue;
+        }
+        let found = hashes.entry(game).or_default();
+        for ini in walk_files(Path::new(&root))
+            .into_iter()
+            .filter(|f| is_ini(f))
+        {
+            if let Ok(bytes) = std::fs::read(&ini) {
+                found.extend(override_hashes(&bytes));
+            }
+        }
+    }
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    for hash in hashes.values().flatten() {
+        *seen.entry(hash.clone()).or_default() += 1;
+    }
+    for set in hashes.values_mut() {
+        set.retain(|h| seen[h] == 1);
+    }
+    HashIndex {
+        built: now_secs(),
+        hashes,
+    }
+}
+
+/// Known hashes per game: learned from the Mods folders, plus any listed in `game_hashes.json`
+fn known_hashes() -> KnownHashes {
+    let path = app_path(HASH_INDEX_FILE);
+    let cached: Option<HashIndex> = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok());
+    let mut index = match cached {
+        Some(index) if now_secs().saturating_sub(index.built) < HASH_INDEX_MAX_AGE => index,
+        _ => {
+            let index = build_hash_index();
+            match serde_json::to_string(&index) {
+                Ok(data) => {
+                    if let Err(e) = std::fs::write(&path, data) {
+                        println!("Failed to cache game hash index: {}", e);
+                    }
+                }
+                Err(e) => println!("Failed to cache game hash index: {}", e),
+            }
+            index
+        }
+    };
+    let extra: KnownHashes = std::fs::read_to_string(app_path(KNOWN_HASHES_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default();
+    for (game, hashes) in extra {
+        index
+            .hashes
+            .entry(game)
+            .or_default()
+            .extend(hashes.into_iter().map(|h| h.to_ascii_lowercase()));
+    }
+    index.hashes
+}
+
+/// Score the ini files under `dir` against every game's signatures and known hashes
+fn score_inis(dir: &Path, expected: &str) -> GameCheck {
+    let signatures = load_signatures();
+    let known = known_hashes();
+    let mut check = GameCheck {
+        expected: expected.to_string(),
+        ..Default::default()
+    };
+    for ini in walk_files(dir).into_iter().filter(|f| is_ini(f)) {
+        let Ok(bytes) = std::fs::read(&ini) else {
+            continue;
+        };
+        check.ini_count += 1;
+        let rel = relative_path(dir, &ini);
+        for hash in override_hashes(&bytes) {
+            let Some((game, _)) = known.iter().find(|(_, hashes)| hashes.contains(&hash)) else {
+                continue;
+            };
+            *check.scores.entry(game.clone()).or_default() += HASH_WEIGHT;
+            check.evidence.push(GameEvidence {
+                game: game.clone(),
+                token: format!("hash {}", hash),
+                ini: rel.clone(),
+                weight: HASH_WEIGHT,
+            });
+        }
+        let text = String::from_utf8_lossy(&bytes).to_ascii_lowercase();
+        for (game, tokens) in &signatures {
+            for (token, weight) in tokens {
+                if !text.contains(token.as_str()) {
+                    continue;
+                }
+                *check.scores.entry(game.clone()).or_default() += weight;
+                check.evidence.push(GameEvidence {
+                    game: game.clone(),
+                    token: token.clone(),
+                    ini: rel.clone(),
+                    weight: *weight,
+                });
+            }
+        }
+    }
+
+    check.detected = check
+        .scores
+        .iter()
+        .filter(|(_, score)| **score >= MIN_SCORE)
+        .max_by_key(|(_, score)| **score)
+        .map(|(game, _)| game.clone());
+    let expected_score = check.scores.get(expected).copied().unwrap_or(0);
+    check.matches = match &check.detected {
+        Some(game) => game == expected || check.scores[game] <= expected_score,
+        None => true,
+    };
+    if !check.matches {
+        let detected = check.detected.clone().unwrap_or_default();
+        check.warning = Some(format!(
+            "This mod looks like a {} mod, not {}",
+            detected, expected
+        ));
+    }
+    check
+}
+
+/// Pull only the ini files out of an archive
+async fn extract_inis(
+    app_handle: &tauri::AppHandle,
+    archive: &Path,
+    dest: &Path,
+) -> Result<(), String> {
+    let program_path = app_handle
+        .path()
+        .resolve("ext/7z.exe", tauri::path::BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    let output = app_handle
+        .shell()
+        .command(program_path.to_string_lossy().to_string())
+        .args([
+            "x",
+            &archive.to_string_lossy(),
+            &format!("-o{}", dest.to_string_lossy()),
+            "*.ini",
+            "-r",
+            "-y",
+        ])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).to_string())
+    }
+}
+
+/// Score on a blocking thread: building the hash index walks every configured Mods folder
+async fn score_blocking(dir: PathBuf, game: String) -> Result<GameCheck, String> {
+    tauri::async_runtime::spawn_blocking(move || score_inis(&dir, &game))
+        .await
+        .map_err(|e| format!("Game check failed: {}", e))
+}
+
+/// Check whether a mod archive or folder belongs to `game`, judging by its ini files.
+/// A mismatch comes back with a warning and the game it most likely belongs to.
+#[tauri::command]
+pub async fn check_mod_game(
+    app_handle: tauri::AppHandle,
+    path: String,
+    game: String,
+) -> Result<GameCheck, String> {
+    let target = if Path::new(&path).is_absolute() {
+        PathBuf::from(&path)
+    } else {
+        app_path(&path)
+    };
+    let check = if target.is_dir() {
+        score_blocking(target, game).await?
+    } else if target.is_file() {
+        let staging = app_path("downloads").join(format!(
+            "gamecheck_{}_{}",
+            now_secs(),
+            target.file_stem().and_then(|s| s.to_str()).unwrap_or("mod")
+        ));
+        let result = extract_inis(&app_handle, &target, &staging).await;
+        let check = match result {
+            Ok(()) => score_blocking(staging.clone(), game).await,
+            Err(e) => Err(e),
+        };
+        let _ = std::fs::remove_dir_all(&staging);
+        check?
+    } else {
+        return Err(format!("{} not found", path));
+    };
+    if let Some(warning) = &check.warning {
+        println!("Game check for {}: {}", path, warning);
+    }
+    Ok(check)
+}
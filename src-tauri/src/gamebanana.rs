@@ -0,0 +1,165 @@
+use once_cell::sync::Lazy;
+use reqwest::{Client, StatusCode};
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+const DEFAULT_API_BASE: &str = "https://gamebanana.com/apiv11/";
+/// Environment variable that overrides the API base, e.g. to point at a local mock
+const API_BASE_ENV: &str = "IMI_GAMEBANANA_API";
+
+static API_BASE: Lazy<RwLock<String>> = Lazy::new(|| {
+    RwLock::new(std::env::var(API_BASE_ENV).unwrap_or_else(|_| DEFAULT_API_BASE.to_string()))
+});
+static CLIENT: Lazy<Client> = Lazy::new(Client::new);
+
+/// A downloadable file listed on a GameBanana mod page
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct RemoteFile {
+    #[serde(rename(deserialize = "_idRow"))]
+    pub id: u64,
+    #[serde(rename(deserialize = "_sFile"))]
+    pub name: String,
+    #[serde(rename(deserialize = "_nFilesize"))]
+    pub size: u64,
+    #[serde(rename(deserialize = "_tsDateAdded"))]
+    pub added: u64,
+    #[serde(rename(deserialize = "_sDownloadUrl"))]
+    pub download_url: String,
+    #[serde(rename(deserialize = "_sVersion"))]
+    pub version: String,
+    #[serde(rename(deserialize = "_sDescription"))]
+    pub description: String,
+}
+
+/// An image of a mod's preview gallery
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all(serialize = "camelCase"))]
+pub struct RemoteImage {
+    #[serde(rename(deserialize = "_sBaseUrl"))]
+    pub base_url: String,
+    #[serde(rename(deserialize = "_sFile"))]
+    pub file: String,
+    #[serde(rename(deserialize = "_sCaption"))]
+    pub caption: String,
+}
+
+impl RemoteImage {
+    /// Full-size image url
+    pub fn url(&self) -> String {
+        format!("{}/{}", self.base_url.trim_end_matches('/'), self.file)
+    }
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RemotePreviewMedia {
+    #[serde(rename = "_aImages")]
+    pub images: Vec<RemoteImage>,
+}
+
+/// The parts of `Mod/<id>/ProfilePage` IMI uses
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(default)]
+pub struct RemoteMod {
+    #[serde(rename = "_idRow")]
+    pub id: u64,
+    #[serde(rename = "_sName")]
+    pub name: String,
+    #[serde(rename = "_sProfileUrl")]
+    pub profile_url: String,
+    #[serde(rename = "_tsDateAdded")]
+    pub date_added: u64,
+    #[serde(rename = "_tsDateUpdated")]
+    pub date_updated: u64,
+    #[serde(rename = "_bIsWithdrawn")]
+    pub withdrawn: bool,
+    #[serde(rename = "_bIsTrashed")]
+    pub trashed: bool,
+    #[serde(rename = "_aFiles")]
+    pub files: Vec<RemoteFile>,
+    #[serde(rename = "_aPreviewMedia")]
+    pub preview_media: RemotePreviewMedia,
+}
+
+impl RemoteMod {
+    pub fn is_available(&self) -> bool {
+        !self.withdrawn && !self.trashed
+    }
+}
+
+#[derive(Debug)]
+pub enum FetchError {
+    /// The mod page no longer exists (deleted, withdrawn or private)
+    Gone,
+    Other(String),
+}
+
+impl std::fmt::Display for FetchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FetchError::Gone => write!(f, "Mod is no longer available on GameBanana"),
+            FetchError::Other(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+pub fn api_base() -> String {
+    let base = API_BASE.read().unwrap().clone();
+    if base.ends_with('/') {
+        base
+    } else {
+        format!("{}/", base)
+    }
+}
+
+/// Fetch the profile page of a mod
+pub async fn fetch_mod(mod_id: u64) -> Result<RemoteMod, FetchError> {
+    let url = format!("{}Mod/{}/ProfilePage", api_base(), mod_id);
+    let response = CLIENT
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| FetchError::Other(e.to_string()))?;
+    match response.status() {
+        StatusCode::NOT_FOUND | StatusCode::GONE | StatusCode::FORBIDDEN => {
+            return Err(FetchError::Gone)
+        }
+        status if !status.is_success() => {
+            return Err(FetchError::Other(format!("API request failed: {}", status)))
+        }
+        _ => {}
+    }
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| FetchError::Other(e.to_string()))?;
+    let value: serde_json::Value =
+        serde_json::from_slice(&body).map_err(|e| FetchError::Other(e.to_string()))?;
+    // Removed mods come back as an error object instead of a profile
+    if value.get("_sErrorCode").is_some() || value.get("_idRow").is_none() {
+        return Err(FetchError::Gone);
+    }
+    serde_json::from_value(value).map_err(|e| FetchError::Other(e.to_string()))
+}
+
+/// Download a file hosted on GameBanana, such as a gallery image
+pub async fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
+    let response = CLIENT.get(url).send().await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Request for {} failed: {}", url, response.status()));
+    }
+    let body = response.bytes().await.map_err(|e| e.to_string())?;
+    Ok(body.to_vec())
+}
+
+/// Point the GameBanana client at another API base (e.g. a local mock server)
+#[tauri::command]
+pub fn set_gamebanana_api_base(url: String) -> Result<(), String> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(format!("Invalid API base url: {}", url));
+    }
+    *API_BASE.write().unwrap() = url;
+    println!("GameBanana API base set to: {}", api_base());
+    Ok(())
+}
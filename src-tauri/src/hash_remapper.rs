@@ -0,0 +1,318 @@
+use crate::app_config::app_path;
+use crate::fs_utils::walk_files;
+use crate::ini_parser::parse_ini;
+use crate::manifest::{now_secs, read_manifest, write_manifest, HashRemapRecord};
+use crate::mod_manager::{is_ini, relative_path};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Per-game mapping files, `hashmaps/<game>.json` or `hashmaps/<game>.txt`
+const HASHMAPS_DIR: &str = "hashmaps";
+/// Ini backups taken when version retention is turned off
+const BACKUPS_DIR: &str = "backups";
+
+/// One `hash =` line that was (or would be) rewritten
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HashChange {
+    pub ini: String,
+    pub section: String,
+    pub line: usize,
+    pub old_hash: String,
+    pub new_hash: String,
+    pub before: String,
+    pub after: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct RemapResult {
+    pub path: String,
+    pub changes: Vec<HashChange>,
+    pub backup: Option<String>,
+    pub error: Option<String>,
+    /// Inis left untouched, such as ones not saved as UTF-8, and why
+    pub warnings: Vec<String>,
+}
+
+fn normalize_hash(hash: &str) -> String {
+    hash.trim().trim_start_matches("0x").to_ascii_lowercase()
+}
+
+/// Read an old→new mapping: a JSON object, or text lines of `old new`, `old = new` or `old,new`
+fn load_mapping(path: &Path) -> Result<HashMap<String, String>, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read hash mapping {:?}: {}", path, e))?;
+    let data = data.trim_start_matches('\u{feff}');
+    let pairs: Vec<(String, String)> = if data.trim_start().starts_with('{') {
+        serde_json::from_str::<HashMap<String, String>>(data)
+            .map_err(|e| format!("Invalid hash mapping {:?}: {}", path, e))?
+            .into_iter()
+            .collect()
+    } else {
+        data.lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with(['#', ';']))
+            .filter_map(|l| {
+                let mut parts = l
+                    .split(|c: char| c == '=' || c == ',' || c.is_whitespace())
+                    .filter(|p| !p.is_empty());
+                Some((parts.next()?.to_string(), parts.next()?.to_string()))
+            })
+            .collect()
+    };
+    let mapping: HashMap<String, String> = pairs
+        .into_iter()
+        .map(|(old, new)| (normalize_hash(&old), normalize_hash(&new)))
+        .filter(|(old, new)| old != new)
+        .collect();
+    if mapping.is_empty() {
+        return Err(format!("Hash mapping {:?} has no entries", path));
+    }
+    Ok(mapping)
+}
+
+/// Mapping file given by the caller, or the one stored for the game
+fn mapping_file(game: &str, mapping: Option<String>) -> Result<PathBuf, String> {
+    if let Some(path) = mapping.filter(|p| !p.is_empty()) {
+        return Ok(PathBuf::from(path));
+    }
+    let dir = app_path(HASHMAPS_DIR);
+    ["json", "txt"]
+        .iter()
+        .map(|ext| dir.join(format!("{}.{}", game, ext)))
+        .find(|p| p.is_file())
+        .ok_or_else(|| format!("No hash mapping found for game '{}' in {:?}", game, dir))
+}
+
+/// Rewrite the `hash =` values of one ini; returns the changes and the new text if anything changed
+fn remap_ini(
+    mod_dir: &Path,
+    ini: &Path,
+    mapping: &HashMap<String, String>,
+) -> Result<(Vec<HashChange>, Option<String>), String> {
+    let bytes = std::fs::read(ini).map_err(|e| format!("Failed to read {:?}: {}", ini, e))?;
+    remap_bytes(&relative_path(mod_dir, ini), bytes, mapping)
+}
+
+/// Rewrite the `hash =` values of ini contents, keeping everything else byte for byte
+fn remap_bytes(
+    ini: &str,
+    bytes: Vec<u8>,
+    mapping: &HashMap<String, String>,
+) -> Result<(Vec<HashChange>, Option<String>), String> {
+    // Only rewrite files that round-trip exactly; lossy decoding would corrupt them
+    let text = String::from_utf8(bytes).map_err(|_| format!("{} is not valid UTF-8", ini))?;
+    let doc = parse_ini(text.trim_start_matches('\u{feff}'));
+    let mut lines: Vec<String> = text.split('\n').map(str::to_string).collect();
+    let mut changes = Vec::new();
+    for section in &doc.sections {
+        for entry in section
+            .entries
+            .iter()
+            .filter(|e| e.key.eq_ignore_ascii_case("hash"))
+        {
+            let Some(new_hash) = mapping.get(&normalize_hash(&entry.value)) else {
+                continue;
+            };
+            let Some(line) = lines.get_mut(entry.line - 1) else {
+                continue;
+            };
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let trailing = &value[value.trim_end().len()..];
+            let after = format!("{}= {}{}", key, new_hash, trailing);
+            changes.push(HashChange {
+                ini: ini.to_string(),
+                section: section.name.clone(),
+                line: entry.line,
+                old_hash: entry.value.clone(),
+                new_hash: new_hash.clone(),
+                before: line.trim_end().to_string(),
+                after: after.trim_end().to_string(),
+            });
+            *line = after;
+        }
+    }
+    let text = (!changes.is_empty()).then(|| lines.join("\n"));
+    Ok((changes, text))
+}
+
+/// Copy the inis about to change into the backups folder
+fn backup_inis(mod_dir: &Path, inis: &[&PathBuf]) -> Result<PathBuf, String> {
+    let name = mod_dir
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("mod");
+    let backup = app_path(BACKUPS_DIR).join(format!("{}_{}", name, now_secs()));
+    for ini in inis {
+        let target = backup.join(relative_path(mod_dir, ini));
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        std::fs::copy(ini, &target).map_err(|e| format!("Failed to back up {:?}: {}", ini, e))?;
+    }
+    Ok(backup)
+}
+
+async fn remap_mod(
+    app_handle: &tauri::AppHandle,
+    mod_dir: &Path,
+    mapping: &HashMap<String, String>,
+    mapping_name: &str,
+    dry_run: bool,
+) -> Result<RemapResult, String> {
+    let mut result = RemapResult {
+        path: mod_dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let mut rewrites = Vec::new();
+    for ini in walk_files(mod_dir).into_iter().filter(|f| is_ini(f)) {
+        // One unreadable ini must not hold back the rest of the mod
+        let (changes, text) = match remap_ini(mod_dir, &ini, mapping) {
+            Ok(remapped) => remapped,
+            Err(e) => {
+                println!("Skipping {:?} while remapping: {}", ini, e);
+                result.warnings.push(e);
+                continue;
+            }
+        };
+        result.changes.extend(changes);
+        if let Some(text) = text {
+            rewrites.push((ini, text));
+        }
+    }
+    if dry_run || rewrites.is_empty() {
+        return Ok(result);
+    }
+
+    // Mods IMI did not install keep having no manifest, so legacy migration can still pick them up
+    let mut manifest = read_manifest(mod_dir);
+    let retained_for = manifest.clone().unwrap_or_default();
+    let backup =
+        match crate::version_store::retain_version(app_handle, mod_dir, &retained_for).await? {
+            Some(record) => {
+                if let Some(manifest) = manifest.as_mut() {
+                    manifest.versions = crate::version_store::load_versions(mod_dir, manifest);
+                }
+                record.id
+            }
+            None => {
+                let inis: Vec<&PathBuf> = rewrites.iter().map(|(ini, _)| ini).collect();
+                backup_inis(mod_dir, &inis)?.to_string_lossy().to_string()
+            }
+        };
+    for (ini, text) in &rewrites {
+        std::fs::write(ini, text).map_err(|e| format!("Failed to write {:?}: {}", ini, e))?;
+    }
+    if let Some(mut manifest) = manifest {
+        manifest.hash_remaps.push(HashRemapRecord {
+            applied: now_secs(),
+            mapping: mapping_name.to_string(),
+            changes: result.changes.len(),
+            backup: backup.clone(),
+        });
+        write_manifest(mod_dir, &manifest)?;
+    }
+    result.backup = Some(backup);
+    Ok(result)
+}
+
+/// Rewrite `hash =` lines of the given mods using a game's old→new hash mapping.
+/// With `dry_run` only the diff is returned; otherwise each changed mod is backed up first.
+#[tauri::command]
+pub async fn remap_mod_hashes(
+    app_handle: tauri::AppHandle,
+    game: String,
+    paths: Vec<String>,
+    mapping: Option<String>,
+    dry_run: bool,
+) -> Result<Vec<RemapResult>, String> {
+    let file = mapping_file(&game, mapping)?;
+    let table = load_mapping(&file)?;
+    let mapping_name = file
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut results = Vec::new();
+    for path in paths {
+        let mod_dir = PathBuf::from(&path);
+        let result = match remap_mod(&app_handle, &mod_dir, &table, &mapping_name, dry_run).await {
+            Ok(result) => result,
+            Err(e) => RemapResult {
+                path,
+                error: Some(e),
+                ..Default::default()
+            },
+        };
+        results.push(result);
+    }
+    println!(
+        "Hash remap for {} with {:?}: {} changes in {} mods{}",
+        game,
+        file,
+        results.iter().map(|r| r.changes.len()).sum::<usize>(),
+        results.iter().filter(|r| !r.changes.is_empty()).count(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mapping() -> HashMap<String, String> {
+        HashMap::from([
+            ("aaaa1111".to_string(), "bbbb2222".to_string()),
+            ("cccc3333".to_string(), "dddd4444".to_string()),
+        ])
+    }
+
+    #[test]
+    fn rewrites_only_mapped_hash_lines() {
+        let ini = "[TextureOverrideBody]\nhash = AAAA1111\nmatch_first_index = 0\n\n[TextureOverrideHead]\nhash = 0x99999999\n";
+        let (changes, text) = remap_bytes("a.ini", ini.as_bytes().to_vec(), &mapping()).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].section, "TextureOverrideBody");
+        assert_eq!(changes[0].line, 2);
+        assert_eq!(
+            text.unwrap(),
+            "[TextureOverrideBody]\nhash = bbbb2222\nmatch_first_index = 0\n\n[TextureOverrideHead]\nhash = 0x99999999\n"
+        );
+    }
+
+    #[test]
+    fn keeps_bom_crlf_and_key_spacing() {
+        let ini = "\u{feff}[TextureOverrideA]\r\n  hash=cccc3333\r\nhandling = skip\r\n";
+        let (_, text) = remap_bytes("a.ini", ini.as_bytes().to_vec(), &mapping()).unwrap();
+        assert_eq!(
+            text.unwrap(),
+            "\u{feff}[TextureOverrideA]\r\n  hash= dddd4444\r\nhandling = skip\r\n"
+        );
+    }
+
+    #[test]
+    fn unchanged_file_is_not_rewritten() {
+        let ini = "[TextureOverrideA]\nhash = 12345678\n";
+        let (changes, text) = remap_bytes("a.ini", ini.as_bytes().to_vec(), &mapping()).unwrap();
+        assert!(changes.is_empty());
+        assert!(text.is_none());
+    }
+
+    #[test]
+    fn hash_lines_in_malformed_sections_are_still_remapped() {
+        let ini = "[TextureOverrideA\nif $x\nhash = aaaa1111\n";
+        let (changes, text) = remap_bytes("a.ini", ini.as_bytes().to_vec(), &mapping()).unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(text.unwrap(), "[TextureOverrideA\nif $x\nhash = bbbb2222\n");
+    }
+
+    #[test]
+    fn non_utf8_files_are_refused() {
+        let ini = b"; caf\xE9\n[TextureOverrideA]\nhash = aaaa1111\n".to_vec();
+        assert!(remap_bytes("a.ini", ini, &mapping()).is_err());
+    }
+}
@@ -0,0 +1,280 @@
+use crate::app_config::{app_path, load_config};
+use crate::fs_utils::hash_bytes;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use warp::http::{header, HeaderValue, StatusCode};
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+const THUMBNAILS_DIR: &str = "thumbnails";
+/// Thumbnail widths handed out; requests snap up to the next one so the cache stays small
+const THUMB_SIZES: &[u32] = &[128, 256, 512, 1024];
+const DEFAULT_THUMB_SIZE: u32 = 256;
+const IMAGE_EXTS: &[&str] = &["png", "jpg", "jpeg", "webp", "gif", "bmp"];
+/// Hosts remote images may be fetched from
+const REMOTE_HOSTS: &[&str] = &["images.gamebanana.com", "gamebanana.com"];
+/// GameBanana image urls never change content, local files are revalidated through the ETag
+const REMOTE_CACHE_CONTROL: &str = "private, max-age=604800, immutable";
+const LOCAL_CACHE_CONTROL: &str = "private, no-cache";
+
+/// Where the preview server listens; the token must accompany every request
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageServerInfo {
+    pub port: u16,
+    pub token: String,
+    pub base_url: String,
+}
+
+static SERVER_INFO: OnceLock<ImageServerInfo> = OnceLock::new();
+
+#[derive(Deserialize)]
+struct ThumbQuery {
+    token: String,
+    /// Local image to serve
+    path: Option<String>,
+    /// Remote image to serve instead
+    url: Option<String>,
+    size: Option<u32>,
+}
+
+/// A random token for this run, from the OS random source
+fn session_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::fill(&mut bytes).map_err(|e| format!("Failed to generate a token: {}", e))?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Compare without stopping at the first difference, so timing does not leak the token
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn snap_size(size: Option<u32>) -> u32 {
+    let size = size.unwrap_or(DEFAULT_THUMB_SIZE);
+    THUMB_SIZES
+        .iter()
+        .copied()
+        .find(|s| *s >= size)
+        .unwrap_or(THUMB_SIZES[THUMB_SIZES.len() - 1])
+}
+
+fn status(code: StatusCode) -> Response {
+    code.into_response()
+}
+
+/// Local images may only come from the configured Mods folders and IMI's own folder
+fn is_allowed_path(path: &Path) -> bool {
+    let Ok(path) = path.canonicalize() else {
+        return false;
+    };
+    let is_image = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| IMAGE_EXTS.contains(&e.to_ascii_lowercase().as_str()));
+    let mut roots: Vec<PathBuf> = load_config()
+        .paths
+        .into_values()
+        .filter(|p| !p.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    roots.push(app_path(""));
+    is_image
+        && roots
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| path.starts_with(root))
+}
+
+fn is_allowed_url(url: &str) -> bool {
+    let Some(rest) = url.strip_prefix("https://") else {
+        return false;
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or("");
+    REMOTE_HOSTS.contains(&host)
+}
+
+/// Downscale an image to `size` wide at most and encode it as a JPEG thumbnail
+fn render_thumbnail(data: &[u8], size: u32, dest: &Path) -> Result<Vec<u8>, String> {
+    let image = image::load_from_memory(data).map_err(|e| e.to_string())?;
+    let thumb = if image.width() > size {
+        image.thumbnail(size, u32::MAX)
+    } else {
+        image
+    };
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    thumb
+        .to_rgb8()
+        .write_to(&mut bytes, image::ImageFormat::Jpeg)
+        .map_err(|e| e.to_string())?;
+    let bytes = bytes.into_inner();
+    if let Some(parent) = dest.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    if let Err(e) = std::fs::write(dest, &bytes) {
+        println!("Failed to cache thumbnail {:?}: {}", dest, e);
+    }
+    Ok(bytes)
+}
+
+fn image_response(bytes: Vec<u8>, etag: &str, cache_control: &'static str) -> Response {
+    let mut response = bytes.into_response();
+    let headers = response.headers_mut();
+    headers.insert(header::CONTENT_TYPE, HeaderValue::from_static("image/jpeg"));
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static(cache_control),
+    );
+    if let Ok(value) = HeaderValue::from_str(etag) {
+        headers.insert(header::ETAG, value);
+    }
+    response
+}
+
+fn not_modified(etag: &str, cache_control: &'static str) -> Response {
+    let mut response = image_response(Vec::new(), etag, cache_control);
+    *response.status_mut() = StatusCode::NOT_MODIFIED;
+    response
+}
+
+async fn serve_thumbnail(query: ThumbQuery, if_none_match: Option<String>) -> Response {
+    let Some(info) = SERVER_INFO.get() else {
+        return status(StatusCode::SERVICE_UNAVAILABLE);
+    };
+    if !tokens_match(&query.token, &info.token) {
+        return status(StatusCode::FORBIDDEN);
+    }
+    let size = snap_size(query.size);
+
+    // The cache key covers the source and, for local files, their last change
+    let (source, cache_control, stamp) = match (&query.path, &query.url) {
+        (Some(path), _) => {
+            let path = Path::new(path);
+            if !is_allowed_path(path) {
+                return status(StatusCode::FORBIDDEN);
+            }
+            let Ok(meta) = path.metadata() else {
+                return status(StatusCode::NOT_FOUND);
+            };
+            let modified = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            (
+                path.to_string_lossy().to_string(),
+                LOCAL_CACHE_CONTROL,
+                format!("{}-{}", modified, meta.len()),
+            )
+        }
+        (None, Some(url)) if is_allowed_url(url) => {
+            (url.clone(), REMOTE_CACHE_CONTROL, String::new())
+        }
+        (None, Some(_)) => return status(StatusCode::FORBIDDEN),
+        (None, None) => return status(StatusCode::BAD_REQUEST),
+    };
+    let key = hash_bytes(format!("{}|{}|{}", source, stamp, size).as_bytes());
+    let etag = format!("\"{}\"", key);
+    if if_none_match.as_deref() == Some(etag.as_str()) {
+        return not_modified(&etag, cache_control);
+    }
+    let cached = app_path(THUMBNAILS_DIR).join(format!("{}.jpg", key));
+    if let Ok(bytes) = std::fs::read(&cached) {
+        return image_response(bytes, &etag, cache_control);
+    }
+
+    let data = if query.path.is_some() {
+        match std::fs::read(&source) {
+            Ok(data) => data,
+            Err(_) => return status(StatusCode::NOT_FOUND),
+        }
+    } else {
+        match reqwest::get(&source).await {
+            Ok(response) if response.status().is_success() => match response.bytes().await {
+                Ok(bytes) => bytes.to_vec(),
+                Err(_) => return status(StatusCode::BAD_GATEWAY),
+            },
+            _ => return status(StatusCode::BAD_GATEWAY),
+        }
+    };
+    let rendered =
+        tauri::async_runtime::spawn_blocking(move || render_thumbnail(&data, size, &cached)).await;
+    match rendered {
+        Ok(Ok(bytes)) => image_response(bytes, &etag, cache_control),
+        Ok(Err(e)) => {
+            println!("Failed to render thumbnail of {}: {}", source, e);
+            status(StatusCode::UNSUPPORTED_MEDIA_TYPE)
+        }
+        Err(_) => status(StatusCode::INTERNAL_SERVER_ERROR),
+    }
+}
+
+/// Start the loopback-only thumbnail server on a free port
+pub fn start() {
+    tauri::async_runtime::spawn(async {
+        let listener = match tokio::net::TcpListener::bind(("127.0.0.1", 0)).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                println!("Failed to start image server: {}", e);
+                return;
+            }
+        };
+        let port = match listener.local_addr() {
+            Ok(addr) => addr.port(),
+            Err(e) => {
+                println!("Failed to start image server: {}", e);
+                return;
+            }
+        };
+        // Without a proper token the server would hand out local files to anyone asking
+        let token = match session_token() {
+            Ok(token) => token,
+            Err(e) => {
+                println!("Failed to start image server: {}", e);
+                return;
+            }
+        };
+        let info = ImageServerInfo {
+            port,
+            token,
+            base_url: format!("http://127.0.0.1:{}", port),
+        };
+        println!("Image server listening on {}", info.base_url);
+        let _ = SERVER_INFO.set(info);
+
+        let thumbnails = warp::get()
+            .and(warp::path!("thumb"))
+            .and(warp::query::<ThumbQuery>())
+            .and(warp::header::optional::<String>("if-none-match"))
+            .then(serve_thumbnail);
+        warp::serve(thumbnails).incoming(listener).run().await;
+    });
+}
+
+/// Address and session token of the thumbnail server, for building image urls
+#[tauri::command]
+pub fn get_image_server() -> Result<ImageServerInfo, String> {
+    SERVER_INFO
+        .get()
+        .cloned()
+        .ok_or_else(|| "Image server is not running".to_string())
+}
+
+/// Drop every cached thumbnail, returning the bytes freed
+#[tauri::command]
+pub fn clear_thumbnail_cache() -> Result<u64, String> {
+    let dir = app_path(THUMBNAILS_DIR);
+    let size = crate::fs_utils::path_size(&dir);
+    if dir.exists() {
+        std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to clear thumbnails: {}", e))?;
+    }
+    println!("Cleared {} of cached thumbnails", crate::format_bytes(size));
+    Ok(size)
+}
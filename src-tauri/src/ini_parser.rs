@@ -0,0 +1,577 @@
+use serde::Serialize;
+use std::path::Path;
+
+/// A statement inside a section: a `key = value` binding, an assignment or a bare command
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniEntry {
+    pub key: String,
+    pub value: String,
+    pub line: usize,
+    /// `pre`/`post` prefix of the statement, if any
+    pub phase: Option<String>,
+    /// Conditions of the enclosing `if`/`else` blocks, joined with `&&`
+    pub condition: Option<String>,
+}
+
+#[derive(Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum SectionKind {
+    TextureOverride,
+    ShaderOverride,
+    ShaderRegex,
+    Resource,
+    CommandList,
+    CustomShader,
+    Key,
+    Constants,
+    Present,
+    Other,
+}
+
+/// Section name prefixes, longest first so `ShaderRegex` is not read as another kind
+const SECTION_KINDS: &[(&str, SectionKind)] = &[
+    ("TextureOverride", SectionKind::TextureOverride),
+    ("ShaderOverride", SectionKind::ShaderOverride),
+    ("ShaderRegex", SectionKind::ShaderRegex),
+    ("CustomShader", SectionKind::CustomShader),
+    ("CommandList", SectionKind::CommandList),
+    ("Constants", SectionKind::Constants),
+    ("Resource", SectionKind::Resource),
+    ("Present", SectionKind::Present),
+    ("Key", SectionKind::Key),
+];
+
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniSection {
+    pub name: String,
+    pub kind: SectionKind,
+    pub line: usize,
+    pub entries: Vec<IniEntry>,
+}
+
+impl IniSection {
+    /// Value of the first entry with this key (case-insensitive)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.key.eq_ignore_ascii_case(key))
+            .map(|e| e.value.as_str())
+    }
+
+    /// Values of every entry with this key (case-insensitive)
+    pub fn get_all(&self, key: &str) -> Vec<String> {
+        self.entries
+            .iter()
+            .filter(|e| e.key.eq_ignore_ascii_case(key))
+            .map(|e| e.value.clone())
+            .collect()
+    }
+
+    /// Whether the section name starts with `prefix` (case-insensitive)
+    pub fn is_kind(&self, prefix: &str) -> bool {
+        starts_with_ignore_case(&self.name, prefix)
+    }
+}
+
+/// Something in the file the parser had to skip or guess about
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniWarning {
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IniDocument {
+    /// `namespace = ...` declared before the first section
+    pub namespace: Option<String>,
+    pub sections: Vec<IniSection>,
+    pub warnings: Vec<IniWarning>,
+}
+
+/// `$variable` declared with `global`/`local`/`persist`
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniVariable {
+    pub name: String,
+    pub section: String,
+    pub initial: Option<String>,
+    pub global: bool,
+    pub persist: bool,
+    pub line: usize,
+}
+
+/// `run = CommandList...` or a `CustomShader` invocation
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniCommandCall {
+    pub section: String,
+    pub target: String,
+    pub line: usize,
+    pub condition: Option<String>,
+}
+
+/// A `[Resource*]` section and the file it loads
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniResource {
+    pub section: String,
+    pub filename: Option<String>,
+    pub resource_type: Option<String>,
+    pub format: Option<String>,
+    /// Whether `filename` exists relative to the ini, when parsed from disk
+    pub exists: Option<bool>,
+    pub line: usize,
+}
+
+/// A `[Key*]` section
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct IniKeyBinding {
+    pub section: String,
+    /// Every `key` line; mods often bind a keyboard and a controller key
+    pub keys: Vec<String>,
+    pub backs: Vec<String>,
+    pub key_type: Option<String>,
+    pub condition: Option<String>,
+    /// `$variable = values` the key cycles through
+    pub variables: Vec<(String, String)>,
+    pub line: usize,
+}
+
+/// Structured view of a 3DMigoto/XXMI ini file
+#[derive(Serialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModIni {
+    pub path: String,
+    pub namespace: Option<String>,
+    pub sections: Vec<IniSection>,
+    pub variables: Vec<IniVariable>,
+    pub commands: Vec<IniCommandCall>,
+    pub resources: Vec<IniResource>,
+    pub keys: Vec<IniKeyBinding>,
+    pub warnings: Vec<IniWarning>,
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.len() >= prefix.len()
+        && text.is_char_boundary(prefix.len())
+        && text[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+fn section_kind(name: &str) -> SectionKind {
+    SECTION_KINDS
+        .iter()
+        .find(|(prefix, _)| starts_with_ignore_case(name, prefix))
+        .map(|(_, kind)| *kind)
+        .unwrap_or(SectionKind::Other)
+}
+
+/// Split a leading keyword (`if`, `pre`, ...) off a statement
+fn leading_word(line: &str) -> (String, &str) {
+    let end = line.find(|c: char| c.is_whitespace()).unwrap_or(line.len());
+    (line[..end].to_ascii_lowercase(), line[end..].trim_start())
+}
+
+/// An open `if` chain: the branches already passed and the condition of the current one
+#[derive(Default)]
+struct IfBlock {
+    earlier: Vec<String>,
+    current: Option<String>,
+}
+
+impl IfBlock {
+    /// A branch only runs when every earlier branch of the chain did not
+    fn condition(&self) -> String {
+        self.earlier
+            .iter()
+            .map(|cond| format!("!({})", cond))
+            .chain(self.current.clone())
+            .collect::<Vec<_>>()
+            .join(" && ")
+    }
+}
+
+/// Parse 3DMigoto ini text; lines that do not make sense are skipped with a warning rather than rejected
+pub fn parse_ini(text: &str) -> IniDocument {
+    let mut doc = IniDocument::default();
+    // One entry per open `if`; `else`/`elif` move the innermost one to its next branch
+    let mut conditions: Vec<IfBlock> = Vec::new();
+    let warn = |doc: &mut IniDocument, line: usize, message: String| {
+        doc.warnings.push(IniWarning { line, message });
+    };
+
+    for (idx, raw) in text.lines().enumerate() {
+        let line_no = idx + 1;
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with(';') {
+            continue;
+        }
+        if let Some(rest) = line.strip_prefix('[') {
+            if !conditions.is_empty() {
+                warn(
+                    &mut doc,
+                    line_no,
+                    "Section starts inside an unclosed if block".to_string(),
+                );
+                conditions.clear();
+            }
+            let name = match rest.split_once(']') {
+                Some((name, _)) => name.trim(),
+                None => {
+                    warn(
+                        &mut doc,
+                        line_no,
+                        "Section header is missing ']'".to_string(),
+                    );
+                    rest.trim()
+                }
+            };
+            doc.sections.push(IniSection {
+                name: name.to_string(),
+                kind: section_kind(name),
+                line: line_no,
+                entries: Vec::new(),
+            });
+            continue;
+        }
+
+        let (word, rest) = leading_word(line);
+        match word.as_str() {
+            "if" => {
+                conditions.push(IfBlock {
+                    earlier: Vec::new(),
+                    current: Some(rest.to_string()),
+                });
+                continue;
+            }
+            "elif" | "else" => {
+                let Some(last) = conditions.last_mut() else {
+                    warn(
+                        &mut doc,
+                        line_no,
+                        format!("'{}' without a matching if", word),
+                    );
+                    continue;
+                };
+                let (next, cond) = leading_word(rest);
+                last.earlier.extend(last.current.take());
+                last.current = if word == "elif" {
+                    Some(rest.to_string())
+                } else if next == "if" {
+                    Some(cond.to_string())
+                } else {
+                    None
+                };
+                continue;
+            }
+            "endif" => {
+                if conditions.pop().is_none() {
+                    warn(
+                        &mut doc,
+                        line_no,
+                        "'endif' without a matching if".to_string(),
+                    );
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let Some(section) = doc.sections.last_mut() else {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim().eq_ignore_ascii_case("namespace") {
+                    doc.namespace = Some(value.trim().to_string());
+                    continue;
+                }
+            }
+            warn(
+                &mut doc,
+                line_no,
+                "Statement outside of any section".to_string(),
+            );
+            continue;
+        };
+        let (phase, statement) = match word.as_str() {
+            "pre" | "post" => (Some(word.clone()), rest),
+            _ => (None, line),
+        };
+        let (key, value) = match statement.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => (statement, ""),
+        };
+        section.entries.push(IniEntry {
+            key: key.to_string(),
+            value: value.to_string(),
+            line: line_no,
+            phase,
+            condition: if conditions.is_empty() {
+                None
+            } else {
+                Some(
+                    conditions
+                        .iter()
+                        .map(IfBlock::condition)
+                        .collect::<Vec<_>>()
+                        .join(" && "),
+                )
+            },
+        });
+    }
+    if !conditions.is_empty() {
+        warn(
+            &mut doc,
+            text.lines().count(),
+            format!("{} if block(s) never closed", conditions.len()),
+        );
+    }
+    doc
+}
+
+/// Parse raw ini bytes, accepting a BOM and invalid UTF-8
+pub fn parse_ini_bytes(bytes: &[u8]) -> IniDocument {
+    let text = String::from_utf8_lossy(bytes);
+    parse_ini(text.trim_start_matches('\u{feff}'))
+}
+
+/// Read and parse an ini file
+pub fn read_ini(path: &Path) -> Result<IniDocument, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    Ok(parse_ini_bytes(&bytes))
+}
+
+/// Parse `global persist $var = 1` style declarations
+fn parse_variable(section: &str, entry: &IniEntry) -> Option<IniVariable> {
+    let mut global = false;
+    let mut persist = false;
+    let mut declared = false;
+    let mut name = None;
+    for word in entry.key.split_whitespace() {
+        match word.to_ascii_lowercase().as_str() {
+            "global" => global = true,
+            "persist" => persist = true,
+            "local" => {}
+            w if w.starts_with('$') => name = Some(word.to_string()),
+            _ => return None,
+        }
+        declared |= !word.starts_with('$');
+    }
+    if !declared {
+        return None;
+    }
+    Some(IniVariable {
+        name: name?,
+        section: section.to_string(),
+        initial: (!entry.value.is_empty()).then(|| entry.value.clone()),
+        global,
+        persist,
+        line: entry.line,
+    })
+}
+
+/// Build the structured model of a parsed ini; `ini_dir` enables resource file checks
+pub fn build_model(doc: IniDocument, path: &str, ini_dir: Option<&Path>) -> ModIni {
+    let mut model = ModIni {
+        path: path.to_string(),
+        namespace: doc.namespace.clone(),
+        warnings: doc.warnings.clone(),
+        ..Default::default()
+    };
+    for section in &doc.sections {
+        for entry in &section.entries {
+            if let Some(variable) = parse_variable(&section.name, entry) {
+                model.variables.push(variable);
+            }
+            let key = entry.key.to_ascii_lowercase();
+            if key == "run" || (key == "checktextureoverride" && section.kind != SectionKind::Key) {
+                model.commands.push(IniCommandCall {
+                    section: section.name.clone(),
+                    target: entry.value.clone(),
+                    line: entry.line,
+                    condition: entry.condition.clone(),
+                });
+            }
+        }
+        match section.kind {
+            SectionKind::Resource => {
+                let filename = section.get("filename").map(str::to_string);
+                let exists = match (&filename, ini_dir) {
+                    (Some(file), Some(dir)) => Some(dir.join(file.replace('\\', "/")).is_file()),
+                    _ => None,
+                };
+                model.resources.push(IniResource {
+                    section: section.name.clone(),
+                    filename,
+                    resource_type: section.get("type").map(str::to_string),
+                    format: section.get("format").map(str::to_string),
+                    exists,
+                    line: section.line,
+                });
+            }
+            SectionKind::Key => {
+                model.keys.push(IniKeyBinding {
+                    section: section.name.clone(),
+                    keys: section.get_all("key"),
+                    backs: section.get_all("back"),
+                    key_type: section.get("type").map(str::to_string),
+                    condition: section.get("condition").map(str::to_string),
+                    variables: section
+                        .entries
+                        .iter()
+                        .filter(|e| e.key.starts_with('$'))
+                        .map(|e| (e.key.clone(), e.value.clone()))
+                        .collect(),
+                    line: section.line,
+                });
+            }
+            _ => {}
+        }
+    }
+    model.sections = doc.sections;
+    model
+}
+
+/// Parse an ini file from disk into its structured model
+pub fn load_mod_ini(path: &Path) -> Result<ModIni, String> {
+    let doc = read_ini(path)?;
+    Ok(build_model(doc, &path.to_string_lossy(), path.parent()))
+}
+
+/// Parse a single 3DMigoto/XXMI ini file
+#[tauri::command]
+pub fn parse_mod_ini(path: String) -> Result<ModIni, String> {
+    load_mod_ini(Path::new(&path))
+}
+
+/// Parse every ini XXMI would load from a mod folder
+#[tauri::command]
+pub fn parse_mod_inis(path: String) -> Vec<ModIni> {
+    crate::mod_manager::active_inis(Path::new(&path))
+        .iter()
+        .filter_map(|ini| match load_mod_ini(ini) {
+            Ok(model) => Some(model),
+            Err(e) => {
+                println!("{}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unclosed_if_is_reported_and_closed_at_next_section() {
+        let doc = parse_ini(
+            "[TextureOverrideBody]\nif $active == 1\nib = ResourceBodyIB\n[KeySwap]\nkey = VK_F1\n",
+        );
+        assert_eq!(doc.sections.len(), 2);
+        let body = &doc.sections[0];
+        assert_eq!(body.entries[0].condition.as_deref(), Some("$active == 1"));
+        assert_eq!(doc.sections[1].entries[0].condition, None);
+        assert!(doc.warnings.iter().any(|w| w.line == 4));
+    }
+
+    #[test]
+    fn unclosed_if_at_end_of_file_is_reported() {
+        let doc = parse_ini("[CommandListA]\nif $a\nif $b\nrun = CommandListB\n");
+        assert_eq!(
+            doc.sections[0].entries[0].condition.as_deref(),
+            Some("$a && $b")
+        );
+        assert!(doc
+            .warnings
+            .iter()
+            .any(|w| w.message.contains("2 if block")));
+    }
+
+    #[test]
+    fn else_negates_the_condition() {
+        let doc = parse_ini("[Present]\nif $a\nx = 1\nelse\nx = 2\nendif\nx = 3\n");
+        let conditions: Vec<_> = doc.sections[0]
+            .entries
+            .iter()
+            .map(|e| e.condition.clone())
+            .collect();
+        assert_eq!(
+            conditions,
+            vec![Some("$a".to_string()), Some("!($a)".to_string()), None]
+        );
+        assert!(doc.warnings.is_empty());
+    }
+
+    #[test]
+    fn chained_branches_negate_every_earlier_branch() {
+        let doc = parse_ini(
+            "[Present]\nif $a\nx = 1\nelif $b\nx = 2\nelse if $c\nx = 3\nelse\nx = 4\nendif\n",
+        );
+        let conditions: Vec<_> = doc.sections[0]
+            .entries
+            .iter()
+            .map(|e| e.condition.clone().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            conditions,
+            vec![
+                "$a",
+                "!($a) && $b",
+                "!($a) && !($b) && $c",
+                "!($a) && !($b) && !($c)"
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_chains_keep_the_outer_condition() {
+        let doc = parse_ini("[Present]\nif $a\nif $b\nelse\nx = 1\nendif\nendif\n");
+        assert_eq!(
+            doc.sections[0].entries[0].condition.as_deref(),
+            Some("$a && !($b)")
+        );
+    }
+
+    #[test]
+    fn stray_endif_is_skipped_with_a_warning() {
+        let doc = parse_ini("[Present]\nendif\nx = 1\n");
+        assert_eq!(doc.sections[0].entries.len(), 1);
+        assert_eq!(doc.warnings.len(), 1);
+    }
+
+    #[test]
+    fn section_header_missing_bracket_still_opens_a_section() {
+        let doc = parse_ini("[ResourceBodyIB\ntype = Buffer\nfilename = Body.ib\n");
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].name, "ResourceBodyIB");
+        assert_eq!(doc.sections[0].kind, SectionKind::Resource);
+        assert_eq!(doc.sections[0].get("filename"), Some("Body.ib"));
+        assert!(doc.warnings[0].message.contains("']'"));
+    }
+
+    #[test]
+    fn bom_is_ignored() {
+        let doc = parse_ini_bytes(b"\xEF\xBB\xBFnamespace = Foo\n[KeyToggle]\nkey = VK_F2\n");
+        assert_eq!(doc.namespace.as_deref(), Some("Foo"));
+        assert_eq!(doc.sections[0].name, "KeyToggle");
+        assert!(doc.warnings.is_empty());
+    }
+
+    #[test]
+    fn non_utf8_input_is_parsed_lossily() {
+        let doc = parse_ini_bytes(b"; caf\xE9\n[TextureOverride\xFFHead]\nhash = 1234abcd\n");
+        assert_eq!(doc.sections.len(), 1);
+        assert_eq!(doc.sections[0].kind, SectionKind::TextureOverride);
+        assert_eq!(doc.sections[0].get("hash"), Some("1234abcd"));
+    }
+
+    #[test]
+    fn statements_outside_sections_are_skipped() {
+        let doc = parse_ini("x = 1\n[Present]\n");
+        assert!(doc.sections[0].entries.is_empty());
+        assert_eq!(doc.warnings.len(), 1);
+    }
+}
@@ -0,0 +1,303 @@
+use crate::ini_parser::load_mod_ini;
+use crate::mod_manager::{active_inis, relative_path, scan_mods};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Keys listed first in a combination, in this order
+const PRIORITY_KEYS: &[&str] = &[
+    "Alt", "Ctrl", "Shift", "Capslock", "Tab", "Up", "Down", "Left", "Right",
+];
+
+/// Virtual key names that read better under another label
+const KEY_LABELS: &[(&str, &str)] = &[
+    ("CONTROL", "Ctrl"),
+    ("LCONTROL", "Ctrl"),
+    ("RCONTROL", "Ctrl"),
+    ("MENU", "Alt"),
+    ("LMENU", "Alt"),
+    ("RMENU", "Alt"),
+    ("LSHIFT", "Shift"),
+    ("RSHIFT", "Shift"),
+    ("CAPITAL", "Capslock"),
+    ("RETURN", "Enter"),
+    ("PRIOR", "PageUp"),
+    ("NEXT", "PageDown"),
+    ("BACK", "Backspace"),
+];
+
+/// One `key` or `back` line of a `[Key*]` section
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Keybind {
+    pub mod_path: String,
+    pub mod_name: String,
+    pub category: String,
+    pub ini: String,
+    pub section: String,
+    pub line: usize,
+    /// Normalized combination, e.g. `Ctrl+F1`
+    pub combo: String,
+    /// The binding as written in the ini
+    pub raw: String,
+    pub back: bool,
+    pub key_type: Option<String>,
+    pub condition: Option<String>,
+}
+
+/// A key combination bound by more than one mod
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KeybindOverlap {
+    pub combo: String,
+    pub mods: Vec<String>,
+    pub categories: Vec<String>,
+    pub binds: Vec<Keybind>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct KeybindReport {
+    pub mods_scanned: usize,
+    pub binds: Vec<Keybind>,
+    pub overlaps: Vec<KeybindOverlap>,
+}
+
+fn key_label(token: &str) -> String {
+    let name = token.strip_prefix("VK_").unwrap_or(token);
+    if let Some((_, label)) = KEY_LABELS
+        .iter()
+        .find(|(vk, _)| vk.eq_ignore_ascii_case(name))
+    {
+        return label.to_string();
+    }
+    if let Some(key) = PRIORITY_KEYS.iter().find(|k| k.eq_ignore_ascii_case(name)) {
+        return key.to_string();
+    }
+    if name.len() <= 3 {
+        return name.to_ascii_uppercase();
+    }
+    let lower = name.to_ascii_lowercase();
+    let mut chars = lower.chars();
+    chars
+        .next()
+        .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// Turn a 3DMigoto key binding into a readable combination; `no_*` modifiers are dropped
+pub fn normalize_combo(raw: &str) -> String {
+    let mut keys: Vec<String> = raw
+        .split_whitespace()
+        .filter(|t| !t.to_ascii_lowercase().starts_with("no_"))
+        .map(key_label)
+        .collect();
+    keys.sort_by_key(|k| {
+        PRIORITY_KEYS
+            .iter()
+            .position(|p| p == k)
+            .unwrap_or(PRIORITY_KEYS.len())
+    });
+    keys.dedup();
+    keys.join("+")
+}
+
+/// Gather the key bindings of every enabled mod of a game
+fn collect_keybinds(root: &Path) -> KeybindReport {
+    let mut report = KeybindReport::default();
+    for installed in scan_mods(root).into_iter().filter(|m| m.enabled) {
+        report.mods_scanned += 1;
+        let mod_dir = PathBuf::from(&installed.path);
+        let category = installed.category_name();
+        for ini in active_inis(&mod_dir) {
+            let model = match load_mod_ini(&ini) {
+                Ok(model) => model,
+                Err(e) => {
+                    println!("{}", e);
+                    continue;
+                }
+            };
+            for binding in &model.keys {
+                let lines = binding
+                    .keys
+                    .iter()
+                    .map(|k| (k, false))
+                    .chain(binding.backs.iter().map(|k| (k, true)));
+                for (raw, back) in lines {
+                    let combo = normalize_combo(raw);
+                    if combo.is_empty() {
+                        continue;
+                    }
+                    report.binds.push(Keybind {
+                        mod_path: installed.path.clone(),
+                        mod_name: installed.name.clone(),
+                        category: category.clone(),
+                        ini: relative_path(&mod_dir, &ini),
+                        section: binding.section.clone(),
+                        line: binding.line,
+                        combo,
+                        raw: raw.clone(),
+                        back,
+                        key_type: binding.key_type.clone(),
+                        condition: binding.condition.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut by_combo: BTreeMap<String, Vec<Keybind>> = BTreeMap::new();
+    for bind in &report.binds {
+        by_combo
+            .entry(bind.combo.to_ascii_lowercase())
+            .or_default()
+            .push(bind.clone());
+    }
+    for binds in by_combo.into_values() {
+        let mods: BTreeSet<String> = binds.iter().map(|b| b.mod_path.clone()).collect();
+        if mods.len() < 2 {
+            continue;
+        }
+        let names: BTreeSet<String> = binds.iter().map(|b| b.mod_name.clone()).collect();
+        let categories: BTreeSet<String> = binds.iter().map(|b| b.category.clone()).collect();
+        report.overlaps.push(KeybindOverlap {
+            combo: binds[0].combo.clone(),
+            mods: names.into_iter().collect(),
+            categories: categories.into_iter().collect(),
+            binds,
+        });
+    }
+    report
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_markdown(category: &str, binds: &[&Keybind], overlapping: &BTreeSet<String>) -> String {
+    let mut out = format!(
+        "# {} keybinds\n\n| Mod | Key | Type | Section |\n|---|---|---|---|\n",
+        category
+    );
+    for bind in binds {
+        let mut key = bind.combo.clone();
+        if bind.back {
+            key += " (back)";
+        }
+        if overlapping.contains(&bind.combo.to_ascii_lowercase()) {
+            key += " ⚠";
+        }
+        out += &format!(
+            "| {} | {} | {} | {} |\n",
+            bind.mod_name.replace('|', "\\|"),
+            key,
+            bind.key_type.as_deref().unwrap_or("activate"),
+            bind.section
+        );
+    }
+    out
+}
+
+fn render_html(category: &str, binds: &[&Keybind], overlapping: &BTreeSet<String>) -> String {
+    let mut rows = String::new();
+    for bind in binds {
+        let class = if overlapping.contains(&bind.combo.to_ascii_lowercase()) {
+            " class=\"overlap\""
+        } else {
+            ""
+        };
+        rows += &format!(
+            "<tr{}><td>{}</td><td>{}{}</td><td>{}</td><td>{}</td></tr>\n",
+            class,
+            escape_html(&bind.mod_name),
+            escape_html(&bind.combo),
+            if bind.back { " (back)" } else { "" },
+            escape_html(bind.key_type.as_deref().unwrap_or("activate")),
+            escape_html(&bind.section)
+        );
+    }
+    format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{0} keybinds</title>\n<style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}td,th{{border:1px solid #888;padding:4px 8px}}.overlap{{background:#fdd}}</style></head>\n<body><h1>{0} keybinds</h1>\n<table><tr><th>Mod</th><th>Key</th><th>Type</th><th>Section</th></tr>\n{1}</table></body></html>\n",
+        escape_html(category),
+        rows
+    )
+}
+
+/// List the key bindings of a game's enabled mods and the combinations bound by more than one mod
+#[tauri::command]
+pub fn list_keybinds(game: String) -> Result<KeybindReport, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    let report = collect_keybinds(&root);
+    println!(
+        "Keybind scan for {}: {} binds in {} mods, {} overlaps",
+        game,
+        report.binds.len(),
+        report.mods_scanned,
+        report.overlaps.len()
+    );
+    Ok(report)
+}
+
+/// Write one keybind cheat-sheet per category (character) into `dir`; `format` is `markdown` or `html`
+#[tauri::command]
+pub fn export_keybind_sheets(
+    game: String,
+    format: String,
+    dir: String,
+) -> Result<Vec<String>, String> {
+    let html = match format.to_ascii_lowercase().as_str() {
+        "markdown" | "md" => false,
+        "html" => true,
+        _ => return Err(format!("Unsupported cheat-sheet format: {}", format)),
+    };
+    let root = crate::app_config::mods_root(&game)?;
+    let report = collect_keybinds(&root);
+    let overlapping: BTreeSet<String> = report
+        .overlaps
+        .iter()
+        .map(|o| o.combo.to_ascii_lowercase())
+        .collect();
+    let mut by_category: BTreeMap<&str, Vec<&Keybind>> = BTreeMap::new();
+    for bind in &report.binds {
+        by_category.entry(&bind.category).or_default().push(bind);
+    }
+
+    let dir = PathBuf::from(dir);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create {:?}: {}", dir, e))?;
+    let mut written = Vec::new();
+    for (category, mut binds) in by_category {
+        binds.sort_by(|a, b| a.mod_name.cmp(&b.mod_name).then(a.line.cmp(&b.line)));
+        let title = if category.is_empty() {
+            "Uncategorized"
+        } else {
+            category
+        };
+        let (content, ext) = if html {
+            (render_html(title, &binds, &overlapping), "html")
+        } else {
+            (render_markdown(title, &binds, &overlapping), "md")
+        };
+        let file_name: String = title
+            .chars()
+            .map(|c| {
+                if c.is_alphanumeric() || c == ' ' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let path = dir.join(format!("{}.{}", file_name.trim(), ext));
+        std::fs::write(&path, content).map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+        written.push(path.to_string_lossy().to_string());
+    }
+    println!(
+        "Exported {} keybind cheat-sheets to {:?}",
+        written.len(),
+        dir
+    );
+    Ok(written)
+}
@@ -0,0 +1,147 @@
+use crate::app_config::{load_config, mods_root};
+use crate::gamebanana::fetch_mod;
+use crate::manifest::{write_manifest, ModManifest};
+use crate::mod_manager::scan_mods;
+use chrono::{Local, NaiveDateTime};
+use serde::Serialize;
+use std::path::Path;
+
+/// Redirect page older installs carry instead of a manifest
+const MOD_PAGE_FILE: &str = "open_mod_page.html";
+const CREATED_AT_MARKER: &str = "createdAt-";
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct MigrationReport {
+    /// Mod folders that received a manifest
+    pub created: Vec<String>,
+    /// Of those, the ones filled in from GameBanana
+    pub enriched: Vec<String>,
+    /// Mods without a manifest or a redirect page to recover one from
+    pub unknown: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+/// `url=` target of a redirect page
+fn parse_mod_page(html: &str) -> Option<String> {
+    html.split_once("url=")
+        .or_else(|| html.split_once("href=\""))
+        .and_then(|(_, rest)| rest.split(['"', '\'', ' ', '>']).next())
+        .map(str::trim)
+        .filter(|url| url.starts_with("http"))
+        .map(str::to_string)
+}
+
+/// Install time from the `createdAt-YYYY-MM-DD-HH-MM-SS` stamp of a redirect page, written in local time
+fn parse_created_at(html: &str) -> Option<u64> {
+    let (_, rest) = html.split_once(CREATED_AT_MARKER)?;
+    let stamp = rest.split(['"', '\'']).next()?;
+    let time = NaiveDateTime::parse_from_str(stamp, "%Y-%m-%d-%H-%M-%S").ok()?;
+    // Clocks set back at the end of daylight saving make some local times ambiguous
+    let local = time.and_local_timezone(Local).earliest()?;
+    u64::try_from(local.timestamp()).ok()
+}
+
+/// When the redirect page was written; copying or syncing the Mods folder moves this,
+/// so it only stands in for a missing `createdAt` stamp
+fn page_written(page: &Path) -> u64 {
+    page.metadata()
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Fill the downloaded file from GameBanana: the newest one that predates the install.
+/// The name stays the local folder name, which reinstalls use as their folder.
+async fn enrich(manifest: &mut ModManifest) -> Result<(), String> {
+    let mod_id = manifest
+        .mod_id()
+        .ok_or_else(|| format!("{} is not a GameBanana mod page", manifest.source))?;
+    let remote = fetch_mod(mod_id).await.map_err(|e| e.to_string())?;
+    let file = remote
+        .files
+        .iter()
+        .filter(|f| manifest.installed == 0 || f.added <= manifest.installed)
+        .max_by_key(|f| f.added)
+        .or_else(|| remote.files.iter().min_by_key(|f| f.added));
+    if let Some(file) = file {
+        manifest.file = file.download_url.clone();
+        manifest.fname = file.name.clone();
+    }
+    Ok(())
+}
+
+/// Create manifests for mods installed before manifests existed, from their `open_mod_page.html`.
+/// With `enrich_remote` the downloaded file is looked up on GameBanana.
+#[tauri::command]
+pub async fn migrate_legacy_installs(
+    games: Option<Vec<String>>,
+    enrich_remote: bool,
+) -> Result<MigrationReport, String> {
+    let games = games.unwrap_or_else(|| {
+        load_config()
+            .paths
+            .into_iter()
+            .filter(|(game, path)| !game.is_empty() && !path.is_empty())
+            .map(|(game, _)| game)
+            .collect()
+    });
+    let mut report = MigrationReport::default();
+    for game in games {
+        let root = match mods_root(&game) {
+            Ok(root) => root,
+            Err(e) => {
+                println!("Skipping {} during migration: {}", game, e);
+                continue;
+            }
+        };
+        for installed in scan_mods(&root) {
+            // Manifests without game and source carry nothing to go on, so they are recovered as well
+            let existing = installed.manifest.clone().unwrap_or_default();
+            if installed.manifest.is_some()
+                && !(existing.game.is_empty() && existing.source.is_empty())
+            {
+                continue;
+            }
+            let mod_dir = Path::new(&installed.path);
+            let page = mod_dir.join(MOD_PAGE_FILE);
+            let html = std::fs::read_to_string(&page).unwrap_or_default();
+            let Some(source) = parse_mod_page(&html) else {
+                report.unknown.push(installed.path.clone());
+                continue;
+            };
+            // No file hashes: the folder may already hold user edits, which updates must keep
+            let mut manifest = ModManifest {
+                name: installed.name.clone(),
+                game: game.clone(),
+                category: installed.category.clone(),
+                source,
+                installed: parse_created_at(&html).unwrap_or_else(|| page_written(&page)),
+                enabled: installed.enabled,
+                hash_remaps: existing.hash_remaps,
+                ..Default::default()
+            };
+            manifest.versions = crate::version_store::load_versions(mod_dir, &manifest);
+            if enrich_remote {
+                match enrich(&mut manifest).await {
+                    Ok(()) => report.enriched.push(installed.path.clone()),
+                    Err(e) => println!("Could not enrich {}: {}", installed.path, e),
+                }
+            }
+            match write_manifest(mod_dir, &manifest) {
+                Ok(()) => report.created.push(installed.path),
+                Err(e) => report.failed.push(format!("{}: {}", installed.path, e)),
+            }
+        }
+    }
+    println!(
+        "Legacy migration: {} manifests created ({} enriched), {} unknown, {} failed",
+        report.created.len(),
+        report.enriched.len(),
+        report.unknown.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
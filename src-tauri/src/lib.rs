@@ -16,6 +16,34 @@ use tauri::{menu::{Menu, MenuItem},
     tray::{MouseButton, TrayIconBuilder, TrayIconEvent},
 };
 use tauri_plugin_deep_link::DeepLinkExt;
+mod app_config;
+mod category_manager;
+mod cold_storage;
+mod conflict_analyzer;
+mod dds_decoder;
+mod dependency_analyzer;
+mod duplicate_finder;
+mod fs_utils;
+mod gallery_manager;
+mod game_detector;
+mod gamebanana;
+mod hash_remapper;
+mod image_server;
+mod ini_parser;
+mod keybind_analyzer;
+mod legacy_migration;
+mod library_store;
+mod manifest;
+mod mod_manager;
+mod modpack_manager;
+mod preview_generator;
+mod profile_manager;
+mod readme_detector;
+mod snapshot_manager;
+mod trash_manager;
+mod update_manager;
+mod user_state;
+mod version_store;
 mod wallpaper_manager;
 const PROGRESS_UPDATE_THRESHOLD: u64 = 1024;
 const BUFFER_SIZE: usize = 8192;
@@ -200,6 +228,24 @@ fn mime_to_extension(mime_type: &str) -> Option<&'static str> {
         .find(|(mime, _)| *mime == clean_mime)
         .map(|(_, ext)| *ext)
 }
+/// File extension of a download, from the final url or else its content type
+fn response_extension(response: &reqwest::Response) -> String {
+    response
+        .url()
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .and_then(|name| std::path::Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .or_else(|| {
+            response
+                .headers()
+                .get("content-type")
+                .and_then(|ct| ct.to_str().ok())
+                .and_then(mime_to_extension)
+        })
+        .unwrap_or("")
+        .to_owned()
+}
 async fn decompress_file(app_handle: tauri::AppHandle, file_path: &str, save_path: &str) -> Result<(), String> {
    let program_path = app_handle
     .path()
@@ -230,6 +276,40 @@ let output = app_handle
         })
     }
 }
+/// Pack everything inside `dir` into a 7z archive at `archive_path`
+async fn compress_dir(app_handle: &tauri::AppHandle, dir: &Path, archive_path: &Path) -> Result<(), String> {
+    let program_path = app_handle
+        .path()
+        .resolve("ext/7z.exe", tauri::path::BaseDirectory::Resource)
+        .map_err(|e| e.to_string())?;
+    if let Some(parent) = archive_path.parent() {
+        create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    let output = app_handle
+        .shell()
+        .command(program_path.to_str().unwrap())
+        .args([
+            "a",
+            &archive_path.to_string_lossy(),
+            &dir.join("*").to_string_lossy(),
+            "-y",
+        ])
+        .output()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let err = String::from_utf8_lossy(&output.stderr);
+        Err(if err.is_empty() {
+            String::from_utf8_lossy(&output.stdout).to_string()
+        } else {
+            err.to_string()
+        })
+    }
+}
 /// Extract archive file (zip, rar, or 7z) to the specified path
 #[tauri::command]
 async fn extract_archive(
@@ -252,6 +332,7 @@ async fn extract_archive(
     let res = decompress_file(app_handle.clone(), file_path.to_str().unwrap(), &save_path);
     let duration = before.elapsed();
     println!("extraction completed in: {:.2?}", duration);
+    let mut readmes = Vec::new();
     if let Err(e) = res.await {
         println!("extraction error: {}", e);
     } else {
@@ -259,11 +340,12 @@ async fn extract_archive(
             safe_remove_file(&file_path)?;
         }
         println!("Archive file removed after extraction");
+        readmes = readme_detector::collect_readmes(Path::new(save_path), true);
     }
     
     if !del {
         app_handle
-            .emit("fin", serde_json::json!({ "key": key, "type": "manual" }))
+            .emit("fin", serde_json::json!({ "key": key, "type": "manual", "readmes": readmes }))
             .map_err(|e| e.to_string())?;
         return Ok(());
     }
@@ -306,7 +388,7 @@ async fn extract_archive(
             ));
         }
         app_handle
-            .emit("fin", serde_json::json!({ "key": key , "type": "auto" }))
+            .emit("fin", serde_json::json!({ "key": key , "type": "auto", "readmes": readmes }))
             .map_err(|e| e.to_string())?;
     }
     Ok(())
@@ -354,21 +436,7 @@ async fn download_and_unzip(
         .await
         .map_err(|e| e.to_string())?;
 
-    let ext = response
-        .url()
-        .path_segments()
-        .and_then(|segments| segments.last())
-        .and_then(|name| std::path::Path::new(name).extension())
-        .and_then(|ext| ext.to_str())
-        .or_else(|| {
-            response
-                .headers()
-                .get("content-type")
-                .and_then(|ct| ct.to_str().ok())
-                .and_then(|ct| mime_to_extension(ct))
-        })
-        .unwrap_or("")
-        .to_owned();
+    let ext = response_extension(&response);
 
     let file_name = if !ext.is_empty() {
         format!("{}.{}", file_name, ext)
@@ -556,6 +624,87 @@ async fn download_and_unzip(
     Ok(())
 }
 
+/// Emit a `download-progress` event for any long-running operation tracked under `key`
+fn emit_progress(app_handle: &tauri::AppHandle, key: &str, done: u64, total: u64, start_time: Instant) {
+    let total_elapsed = start_time.elapsed().as_secs_f64();
+    let avg_speed = if total_elapsed > 0.0 {
+        done as f64 / total_elapsed
+    } else {
+        0.0
+    };
+    let eta_secs = if avg_speed > 0.0 {
+        (total.saturating_sub(done) as f64 / avg_speed) as u64
+    } else {
+        0
+    };
+    let _ = app_handle.emit(
+        "download-progress",
+        DownloadProgress {
+            downloaded: done as f64,
+            total: total.max(done) as f64,
+            speed: format_speed(avg_speed),
+            eta: format_duration(eta_secs),
+            key: key.to_string(),
+        },
+    );
+}
+
+/// Download a file into `dir` outside the install queue, emitting progress under `key`
+async fn download_file(
+    app_handle: &tauri::AppHandle,
+    download_url: &str,
+    dir: &Path,
+    file_name: &str,
+    key: &str,
+) -> Result<std::path::PathBuf, String> {
+    println!("Downloading {} from URL: {}", file_name, download_url);
+    let response = Client::new()
+        .get(download_url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Download failed: {}", response.status()));
+    }
+    let ext = response_extension(&response);
+    let file_name = if !ext.is_empty() {
+        format!("{}.{}", file_name, ext)
+    } else {
+        file_name.to_string()
+    };
+    let total_size = response.content_length().unwrap_or(0);
+
+    create_dir_all(dir).map_err(|e| format!("Failed to create directory: {}", e))?;
+    let file_path = dir.join(&file_name);
+    let file = File::create(&file_path).map_err(|e| e.to_string())?;
+    let mut writer = BufWriter::with_capacity(BUFFER_SIZE, file);
+
+    let mut stream = response.bytes_stream();
+    let mut downloaded: u64 = 0;
+    let mut last_progress_update: u64 = 0;
+    let start_time = Instant::now();
+
+    while let Some(item) = stream.next().await {
+        let chunk = item.map_err(|e| e.to_string())?;
+        writer.write_all(&chunk).map_err(|e| e.to_string())?;
+        downloaded += chunk.len() as u64;
+
+        if (downloaded - last_progress_update) >= PROGRESS_UPDATE_THRESHOLD {
+            emit_progress(app_handle, key, downloaded, total_size, start_time);
+            last_progress_update = downloaded;
+        }
+    }
+    writer.flush().map_err(|e| e.to_string())?;
+
+    println!(
+        "Download completed for '{}': {} in {:.2}s",
+        file_name,
+        format_bytes(downloaded),
+        start_time.elapsed().as_secs_f64()
+    );
+    Ok(file_path)
+}
+
 #[tauri::command]
 fn cancel_install(key: String) -> Result<(), String> {
     let mut counts = DOWNLOAD_COUNTS.write().unwrap();
@@ -644,6 +793,7 @@ pub fn run() {
         .plugin(tauri_plugin_deep_link::init())
         .setup(|app| {
             set_cwd().unwrap();
+            image_server::start();
             #[cfg(desktop)]
             app.deep_link().register_all()?;
             wallpaper_manager::init_wallpaper()?;
@@ -694,7 +844,64 @@ pub fn run() {
             get_cwd,
             set_cwd,
             extract_archive,
-            wallpaper_manager::get_wallpaper
+            wallpaper_manager::get_wallpaper,
+            manifest::save_mod_manifest,
+            manifest::get_mod_manifest,
+            mod_manager::set_mod_enabled,
+            mod_manager::set_mods_enabled,
+            mod_manager::set_mod_inis_enabled,
+            mod_manager::list_mods,
+            profile_manager::list_profiles,
+            profile_manager::save_profile,
+            profile_manager::delete_profile,
+            profile_manager::apply_profile,
+            gamebanana::set_gamebanana_api_base,
+            update_manager::check_mod_updates,
+            update_manager::update_mod,
+            version_store::list_mod_versions,
+            version_store::retain_mod_version,
+            version_store::rollback_mod,
+            trash_manager::uninstall_mod,
+            trash_manager::list_trash,
+            trash_manager::restore_from_trash,
+            trash_manager::delete_from_trash,
+            trash_manager::purge_trash,
+            trash_manager::empty_trash,
+            snapshot_manager::create_snapshot,
+            snapshot_manager::list_snapshots,
+            snapshot_manager::restore_snapshot,
+            snapshot_manager::delete_snapshot,
+            conflict_analyzer::find_hash_conflicts,
+            ini_parser::parse_mod_ini,
+            ini_parser::parse_mod_inis,
+            keybind_analyzer::list_keybinds,
+            keybind_analyzer::export_keybind_sheets,
+            hash_remapper::remap_mod_hashes,
+            game_detector::check_mod_game,
+            dependency_analyzer::find_missing_dependencies,
+            user_state::get_mod_user_state,
+            user_state::reset_mod_user_state,
+            duplicate_finder::find_duplicate_mods,
+            modpack_manager::export_modpack,
+            modpack_manager::import_modpack,
+            modpack_manager::reinstall_from_manifests,
+            legacy_migration::migrate_legacy_installs,
+            library_store::list_library,
+            library_store::add_to_library,
+            library_store::set_library_mod_enabled,
+            cold_storage::archive_mod,
+            cold_storage::restore_mod,
+            cold_storage::get_cold_storage_usage,
+            preview_generator::generate_mod_preview,
+            preview_generator::generate_missing_previews,
+            image_server::get_image_server,
+            image_server::clear_thumbnail_cache,
+            gallery_manager::get_mod_gallery,
+            gallery_manager::download_mod_gallery,
+            readme_detector::get_mod_readme,
+            category_manager::move_mod,
+            category_manager::rename_mod,
+            category_manager::recategorize_mods
         ]).on_window_event(|window, event| {
             if let tauri::WindowEvent::CloseRequested { api, .. } = event {
                 let _ = window.hide();
@@ -0,0 +1,202 @@
+use crate::app_config::{app_path, mods_root};
+use crate::fs_utils::{move_path, replace_dir};
+use crate::manifest::{read_manifest, write_manifest, ModManifest};
+use crate::mod_manager::{disabled_name, is_disabled_name, mod_key, scan_mods};
+use serde::Serialize;
+use std::path::{Component, Path, PathBuf};
+
+/// Mods kept once per game, linked into the Mods folder while enabled
+const LIBRARY_DIR: &str = "library";
+
+/// A mod stored in the library
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LibraryMod {
+    pub key: String,
+    /// Folder in the library holding the files
+    pub path: String,
+    /// Link in the Mods folder, if any
+    pub link: Option<String>,
+    pub enabled: bool,
+    pub manifest: Option<ModManifest>,
+}
+
+pub fn library_root(game: &str) -> PathBuf {
+    app_path(LIBRARY_DIR).join(game)
+}
+
+/// Library folder of a mod key; keys come from the frontend, so they must stay inside the library
+fn stored_path(game: &str, key: &str) -> Result<PathBuf, String> {
+    let path = Path::new(key);
+    if key.is_empty()
+        || path.has_root()
+        || !path.components().all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("Invalid library key: '{}'", key));
+    }
+    Ok(library_root(game).join(path))
+}
+
+/// Whether a path is a symlink or junction rather than a real folder
+pub fn is_link(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+/// Folder a link points to; the path itself when it is not a link
+pub fn resolve_link(path: &Path) -> PathBuf {
+    if !is_link(path) {
+        return path.to_path_buf();
+    }
+    match std::fs::read_link(path) {
+        Ok(target) => {
+            let target = target.to_string_lossy().to_string();
+            PathBuf::from(
+                target
+                    .strip_prefix(r"\\?\")
+                    .or_else(|| target.strip_prefix(r"\??\"))
+                    .unwrap_or(&target),
+            )
+        }
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+/// Link `link` to the folder `target`: a junction on Windows, which needs no admin rights
+pub fn create_dir_link(target: &Path, link: &Path) -> Result<(), String> {
+    if std::fs::symlink_metadata(link).is_ok() {
+        return Err(format!("{:?} already exists", link));
+    }
+    if let Some(parent) = link.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    // Created directly rather than through `mklink`, so names with `&`, `^` or `%` stay literal
+    #[cfg(target_os = "windows")]
+    junction::create(target, link)
+        .map_err(|e| format!("Failed to link {:?} to {:?}: {}", link, target, e))?;
+    #[cfg(not(target_os = "windows"))]
+    std::os::unix::fs::symlink(target, link)
+        .map_err(|e| format!("Failed to link {:?} to {:?}: {}", link, target, e))?;
+    println!("Linked {:?} -> {:?}", link, target);
+    Ok(())
+}
+
+/// Remove a link without touching what it points to; refuses anything that is not a link
+pub fn remove_link(link: &Path) -> Result<(), String> {
+    if !is_link(link) {
+        return Err(format!("Refusing to remove {:?}: not a library link", link));
+    }
+    // Junctions are removed like folders, symlinks like files
+    std::fs::remove_dir(link)
+        .or_else(|_| std::fs::remove_file(link))
+        .map_err(|e| format!("Failed to remove link {:?}: {}", link, e))?;
+    println!("Unlinked {:?}", link);
+    Ok(())
+}
+
+/// Link of a library mod in the Mods folder, enabled or renamed with the DISABLED marker
+pub fn find_link(root: &Path, key: &str) -> Option<PathBuf> {
+    let enabled = root.join(key);
+    if is_link(&enabled) {
+        return Some(enabled);
+    }
+    let disabled = match key.rsplit_once('/') {
+        Some((parent, name)) => root.join(parent).join(disabled_name(name)),
+        None => root.join(disabled_name(key)),
+    };
+    is_link(&disabled).then_some(disabled)
+}
+
+/// Every mod in a game's library with its link state
+pub fn library_mods(game: &str, root: &Path) -> Vec<LibraryMod> {
+    scan_mods(&library_root(game))
+        .into_iter()
+        .map(|stored| {
+            let link = find_link(root, &stored.key);
+            let enabled = link.as_ref().is_some_and(|l| {
+                l.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| !is_disabled_name(n))
+            });
+            LibraryMod {
+                key: stored.key,
+                path: stored.path,
+                link: link.map(|l| l.to_string_lossy().to_string()),
+                enabled,
+                manifest: stored.manifest,
+            }
+        })
+        .collect()
+}
+
+fn set_enabled(game: &str, root: &Path, key: &str, enabled: bool) -> Result<LibraryMod, String> {
+    let stored = stored_path(game, key)?;
+    if !stored.is_dir() {
+        return Err(format!("'{}' is not in the {} library", key, game));
+    }
+    if let Some(link) = find_link(root, key) {
+        remove_link(&link)?;
+    }
+    if enabled {
+        create_dir_link(&stored, &root.join(key))?;
+    }
+    if let Some(mut manifest) = read_manifest(&stored) {
+        manifest.enabled = enabled;
+        write_manifest(&stored, &manifest)?;
+    }
+    library_mods(game, root)
+        .into_iter()
+        .find(|m| m.key == key)
+        .ok_or_else(|| format!("'{}' is not in the {} library", key, game))
+}
+
+#[tauri::command]
+pub fn list_library(game: String) -> Result<Vec<LibraryMod>, String> {
+    let root = mods_root(&game)?;
+    Ok(library_mods(&game, &root))
+}
+
+/// Move mod folders out of the Mods folder into the library, linking back the enabled ones.
+/// A folder whose key is already stored replaces the stored copy.
+#[tauri::command]
+pub fn add_to_library(game: String, paths: Vec<String>) -> Result<Vec<LibraryMod>, String> {
+    let root = mods_root(&game)?;
+    let mut added = Vec::new();
+    for path in paths {
+        let mod_dir = PathBuf::from(&path);
+        if is_link(&mod_dir) {
+            continue;
+        }
+        if !mod_dir.is_dir() {
+            return Err(format!("Mod folder not found: {}", path));
+        }
+        let key = mod_key(&root, &mod_dir);
+        let name = mod_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let enabled = !is_disabled_name(name);
+        let stored = stored_path(&game, &key)?;
+        // A reinstall replaces the stored copy; the previous one was retained as a version
+        if stored.exists() {
+            replace_dir(&stored, &mod_dir)?;
+        } else {
+            move_path(&mod_dir, &stored)?;
+        }
+        added.push(set_enabled(&game, &root, &key, enabled)?);
+    }
+    println!("Added {} mods to the {} library", added.len(), game);
+    Ok(added)
+}
+
+/// Enable a library mod by linking it into the Mods folder, or disable it by removing the link.
+/// An archived mod is restored before it is linked.
+#[tauri::command]
+pub async fn set_library_mod_enabled(
+    app_handle: tauri::AppHandle,
+    game: String,
+    key: String,
+    enabled: bool,
+) -> Result<LibraryMod, String> {
+    let root = mods_root(&game)?;
+    if enabled {
+        crate::cold_storage::ensure_restored(&app_handle, &stored_path(&game, &key)?).await?;
+    }
+    set_enabled(&game, &root, &key, enabled)
+}
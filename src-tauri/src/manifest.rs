@@ -0,0 +1,208 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File name of the per-mod install manifest, stored at the root of the mod folder
+pub const MANIFEST_FILE: &str = "imi_manifest.json";
+
+/// Install metadata recorded for every mod IMI installs
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ModManifest {
+    pub name: String,
+    pub game: String,
+    pub category: String,
+    pub source: String,
+    pub file: String,
+    pub fname: String,
+    pub preview: String,
+    /// Gallery images saved next to the mod, the first one being its preview
+    pub gallery: Vec<GalleryImage>,
+    pub installed: u64,
+    pub updated: u64,
+    pub enabled: bool,
+    pub disabled_inis: Vec<String>,
+    /// Content hashes of the files the installed archive shipped, by relative path
+    pub files: BTreeMap<String, String>,
+    /// Earlier versions kept for rollback, oldest first
+    pub versions: Vec<VersionRecord>,
+    /// Hash remaps applied after game updates, oldest first
+    pub hash_remaps: Vec<HashRemapRecord>,
+}
+
+/// A GameBanana gallery image stored in the mod folder
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GalleryImage {
+    /// File name inside the mod folder
+    pub file: String,
+    pub source: String,
+    pub caption: String,
+}
+
+/// A previous version of a mod retained in the app-managed version store
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct VersionRecord {
+    pub id: String,
+    pub file: String,
+    pub fname: String,
+    pub updated: u64,
+    pub installed: u64,
+    pub retained: u64,
+    pub location: String,
+    pub compressed: bool,
+}
+
+/// A hash remap applied to the mod's ini files
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct HashRemapRecord {
+    pub applied: u64,
+    /// Mapping file the hashes came from
+    pub mapping: String,
+    pub changes: usize,
+    /// Retained version id or backup folder holding the files from before the remap
+    pub backup: String,
+}
+
+/// A download in the shape of the frontend's `DownloadItem`, ready for its download queue
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedDownload {
+    pub status: String,
+    pub game: String,
+    pub preview: String,
+    pub category: String,
+    pub source: String,
+    pub file: String,
+    pub name: String,
+    pub fname: String,
+    pub key: String,
+    pub game_path: String,
+    pub categorized: bool,
+    pub updated: u64,
+}
+
+impl ModManifest {
+    /// GameBanana mod id parsed from the source url (`.../mods/<id>`)
+    pub fn mod_id(&self) -> Option<u64> {
+        id_after(&self.source, "mods/")
+    }
+
+    /// GameBanana file id parsed from the download url (`.../dl/<id>`)
+    pub fn file_id(&self) -> Option<u64> {
+        id_after(&self.file, "dl/")
+    }
+
+    /// Queue entry that downloads this mod's file again
+    pub fn to_download(&self) -> QueuedDownload {
+        let config = crate::app_config::load_config();
+        let millis = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis())
+            .unwrap_or(0);
+        QueuedDownload {
+            status: "pending".to_string(),
+            game: self.game.clone(),
+            preview: self.preview.clone(),
+            category: self.category.clone(),
+            source: self.source.clone(),
+            file: self.file.clone(),
+            name: self.name.clone(),
+            fname: self.fname.clone(),
+            key: format!(
+                "{}{}{}{}",
+                millis,
+                self.game,
+                self.mod_id().unwrap_or(0),
+                self.file_id().unwrap_or(0)
+            ),
+            game_path: config.paths.get(&self.game).cloned().unwrap_or_default(),
+            categorized: config.categorized.unwrap_or(true),
+            updated: self.updated,
+        }
+    }
+}
+
+fn id_after(url: &str, marker: &str) -> Option<u64> {
+    url.split(marker)
+        .nth(1)?
+        .split(['/', '?', '#'])
+        .next()?
+        .parse()
+        .ok()
+}
+
+/// Current unix time in seconds
+pub fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Files IMI adds to a mod folder itself rather than taking from the archive
+pub fn is_imi_file(rel_path: &str) -> bool {
+    rel_path == MANIFEST_FILE
+        || rel_path == "open_mod_page.html"
+        || rel_path == crate::cold_storage::COLD_STUB_FILE
+        || rel_path.starts_with("preview.")
+}
+
+pub fn manifest_path(mod_dir: &Path) -> PathBuf {
+    mod_dir.join(MANIFEST_FILE)
+}
+
+/// Read the manifest of a mod folder, if it has one
+pub fn read_manifest(mod_dir: &Path) -> Option<ModManifest> {
+    let data = std::fs::read_to_string(manifest_path(mod_dir)).ok()?;
+    match serde_json::from_str(&data) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            println!("Ignoring unreadable manifest in {:?}: {}", mod_dir, e);
+            None
+        }
+    }
+}
+
+pub fn write_manifest(mod_dir: &Path, manifest: &ModManifest) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(manifest).map_err(|e| e.to_string())?;
+    std::fs::write(manifest_path(mod_dir), data)
+        .map_err(|e| format!("Failed to write manifest for {:?}: {}", mod_dir, e))
+}
+
+/// Find the closest folder at or above `path` that carries a manifest
+pub fn find_manifest_dir(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.is_dir() && manifest_path(dir).is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Record the manifest of a freshly installed mod
+#[tauri::command]
+pub fn save_mod_manifest(path: String, mut manifest: ModManifest) -> Result<(), String> {
+    let mod_dir = Path::new(&path);
+    if !mod_dir.is_dir() {
+        return Err(format!("Mod folder not found: {}", path));
+    }
+    if manifest.installed == 0 {
+        manifest.installed = now_secs();
+    }
+    if manifest.files.is_empty() {
+        manifest.files = crate::fs_utils::hash_tree(mod_dir, is_imi_file);
+    }
+    if manifest.versions.is_empty() {
+        manifest.versions = crate::version_store::load_versions(mod_dir, &manifest);
+    }
+    manifest.enabled = !crate::mod_manager::is_disabled_name(
+        mod_dir.file_name().and_then(|n| n.to_str()).unwrap_or(""),
+    );
+    write_manifest(mod_dir, &manifest)
+}
+
+#[tauri::command]
+pub fn get_mod_manifest(path: String) -> Option<ModManifest> {
+    read_manifest(Path::new(&path))
+}
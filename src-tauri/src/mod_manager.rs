@@ -0,0 +1,319 @@
+use crate::manifest::{
+    find_manifest_dir, manifest_path, read_manifest, write_manifest, ModManifest,
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+/// XXMI skips any folder or ini whose name starts with this (case-insensitive)
+const DISABLED_MARKER: &str = "DISABLED";
+/// Prefix IMI puts in front of names it disables
+const DISABLED_PREFIX: &str = "DISABLED_";
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ToggleResult {
+    path: String,
+    new_path: Option<String>,
+    error: Option<String>,
+}
+
+/// A mod folder found under a game's Mods root
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct InstalledMod {
+    pub path: String,
+    pub key: String,
+    pub name: String,
+    pub category: String,
+    pub enabled: bool,
+    /// Files packed away in cold storage until the mod is enabled again
+    pub archived: bool,
+    pub manifest: Option<ModManifest>,
+}
+
+impl InstalledMod {
+    /// Category from the manifest, falling back to the folder the mod sits in
+    pub fn category_name(&self) -> String {
+        self.manifest
+            .as_ref()
+            .map(|m| m.category.clone())
+            .filter(|c| !c.is_empty())
+            .unwrap_or_else(|| self.category.clone())
+    }
+}
+
+/// Check whether XXMI would skip a file or folder with this name
+pub fn is_disabled_name(name: &str) -> bool {
+    name.len() >= DISABLED_MARKER.len()
+        && name.is_char_boundary(DISABLED_MARKER.len())
+        && name[..DISABLED_MARKER.len()].eq_ignore_ascii_case(DISABLED_MARKER)
+}
+
+/// Strip the DISABLED marker and any separator that follows it
+pub fn enabled_name(name: &str) -> String {
+    if !is_disabled_name(name) {
+        return name.to_string();
+    }
+    let stripped = name[DISABLED_MARKER.len()..].trim_start_matches([' ', '_', '-']);
+    if stripped.is_empty() {
+        name.to_string()
+    } else {
+        stripped.to_string()
+    }
+}
+
+pub fn disabled_name(name: &str) -> String {
+    if is_disabled_name(name) {
+        name.to_string()
+    } else {
+        format!("{}{}", DISABLED_PREFIX, name)
+    }
+}
+
+/// Pick a free path in `dir` for `name`, appending ` (n)` before the extension on collision
+pub fn unique_path(dir: &Path, name: &str, is_file: bool) -> PathBuf {
+    let candidate = dir.join(name);
+    if !candidate.exists() {
+        return candidate;
+    }
+    let (stem, ext) = match name.rfind('.') {
+        Some(idx) if is_file && idx > 0 => (&name[..idx], &name[idx..]),
+        _ => (name, ""),
+    };
+    let mut counter = 1;
+    loop {
+        let candidate = dir.join(format!("{} ({}){}", stem, counter, ext));
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+fn file_name_of(path: &Path) -> Result<&str, String> {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {:?}", path))
+}
+
+pub fn is_ini(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("ini"))
+}
+
+/// Relative path of `path` inside `root`, using forward slashes
+pub fn relative_path(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Stable identifier of a mod: its path relative to the Mods root without the DISABLED marker
+pub fn mod_key(root: &Path, mod_dir: &Path) -> String {
+    let rel = relative_path(root, mod_dir);
+    match rel.rsplit_once('/') {
+        Some((parent, name)) => format!("{}/{}", parent, enabled_name(name)),
+        None => enabled_name(&rel),
+    }
+}
+
+fn sub_dirs(dir: &Path) -> Vec<PathBuf> {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A folder is a mod if IMI installed or archived it, or it has an ini at its top level
+fn is_mod_dir(dir: &Path) -> bool {
+    if manifest_path(dir).is_file() || crate::cold_storage::is_archived(dir) {
+        return true;
+    }
+    std::fs::read_dir(dir)
+        .map(|mut entries| {
+            entries.any(|entry| entry.is_ok_and(|e| e.path().is_file() && is_ini(&e.path())))
+        })
+        .unwrap_or(false)
+}
+
+fn installed_mod(root: &Path, mod_dir: &Path, category: &str) -> InstalledMod {
+    let name = mod_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    InstalledMod {
+        path: mod_dir.to_string_lossy().to_string(),
+        key: mod_key(root, mod_dir),
+        name: enabled_name(name),
+        category: category.to_string(),
+        enabled: !is_disabled_name(name),
+        archived: crate::cold_storage::is_archived(mod_dir),
+        manifest: read_manifest(mod_dir),
+    }
+}
+
+/// Collect the mods under a Mods root, looking one level into category folders
+pub fn scan_mods(root: &Path) -> Vec<InstalledMod> {
+    let mut mods = Vec::new();
+    for dir in sub_dirs(root) {
+        let children = sub_dirs(&dir);
+        if is_mod_dir(&dir) || !children.iter().any(|child| is_mod_dir(child)) {
+            mods.push(installed_mod(root, &dir, ""));
+            continue;
+        }
+        let category = dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("")
+            .to_string();
+        for child in children {
+            mods.push(installed_mod(root, &child, &category));
+        }
+    }
+    mods.sort_by(|a, b| a.key.cmp(&b.key));
+    mods
+}
+
+/// Ini files XXMI would load from a mod: skips anything under a DISABLED file or folder
+pub fn active_inis(mod_dir: &Path) -> Vec<PathBuf> {
+    crate::fs_utils::walk_files(mod_dir)
+        .into_iter()
+        .filter(|file| is_ini(file))
+        .filter(|file| {
+            file.strip_prefix(mod_dir)
+                .map(|rel| {
+                    !rel.components()
+                        .any(|c| c.as_os_str().to_str().is_some_and(is_disabled_name))
+                })
+                .unwrap_or(false)
+        })
+        .collect()
+}
+
+/// Rename a mod folder or ini into the requested state and return its new path
+pub fn toggle_path(path: &Path, enabled: bool) -> Result<PathBuf, String> {
+    let is_file = path.is_file();
+    if !(path.is_dir() || (is_file && is_ini(path))) {
+        return Err(format!("Not a mod folder or ini file: {:?}", path));
+    }
+    let name = file_name_of(path)?;
+    if is_disabled_name(name) != enabled {
+        return Ok(path.to_path_buf());
+    }
+    let target_name = if enabled {
+        enabled_name(name)
+    } else {
+        disabled_name(name)
+    };
+    let parent = path
+        .parent()
+        .ok_or_else(|| format!("Path has no parent folder: {:?}", path))?;
+    let target = unique_path(parent, &target_name, is_file);
+    std::fs::rename(path, &target)
+        .map_err(|e| format!("Failed to rename {:?} to {:?}: {}", path, target, e))?;
+    println!("Renamed {:?} -> {:?}", path, target);
+
+    if is_file {
+        sync_ini_manifest(path, &target, enabled);
+    } else {
+        sync_mod_manifest(&target, enabled);
+    }
+    Ok(target)
+}
+
+fn sync_mod_manifest(mod_dir: &Path, enabled: bool) {
+    let Some(mut manifest) = read_manifest(mod_dir) else {
+        return;
+    };
+    // The name is left alone: it may be a display name, and renames update it themselves
+    manifest.enabled = enabled;
+    if let Err(e) = write_manifest(mod_dir, &manifest) {
+        println!("{}", e);
+    }
+}
+
+fn sync_ini_manifest(old: &Path, new: &Path, enabled: bool) {
+    let Some(mod_dir) = new.parent().and_then(find_manifest_dir) else {
+        return;
+    };
+    let Some(mut manifest) = read_manifest(&mod_dir) else {
+        return;
+    };
+    let old_rel = relative_path(&mod_dir, old);
+    manifest.disabled_inis.retain(|ini| *ini != old_rel);
+    if !enabled {
+        manifest.disabled_inis.push(relative_path(&mod_dir, new));
+    }
+    if let Err(e) = write_manifest(&mod_dir, &manifest) {
+        println!("{}", e);
+    }
+}
+
+/// Enable or disable a mod folder or one of its ini files; an archived mod is restored first
+#[tauri::command]
+pub async fn set_mod_enabled(
+    app_handle: tauri::AppHandle,
+    path: String,
+    enabled: bool,
+) -> Result<String, String> {
+    if enabled {
+        crate::cold_storage::ensure_restored(&app_handle, Path::new(&path)).await?;
+    }
+    toggle_path(Path::new(&path), enabled).map(|p| p.to_string_lossy().to_string())
+}
+
+pub fn toggle_result(path: String, result: Result<PathBuf, String>) -> ToggleResult {
+    match result {
+        Ok(new_path) => ToggleResult {
+            path,
+            new_path: Some(new_path.to_string_lossy().to_string()),
+            error: None,
+        },
+        Err(e) => ToggleResult {
+            path,
+            new_path: None,
+            error: Some(e),
+        },
+    }
+}
+
+/// Enable or disable several mods or ini files, reporting the outcome of each
+#[tauri::command]
+pub async fn set_mods_enabled(
+    app_handle: tauri::AppHandle,
+    paths: Vec<String>,
+    enabled: bool,
+) -> Vec<ToggleResult> {
+    let mut results = Vec::new();
+    for path in paths {
+        let restored = if enabled {
+            crate::cold_storage::ensure_restored(&app_handle, Path::new(&path)).await
+        } else {
+            Ok(())
+        };
+        let result = restored.and_then(|_| toggle_path(Path::new(&path), enabled));
+        results.push(toggle_result(path, result));
+    }
+    results
+}
+
+/// Enable or disable every ini file inside a mod folder
+#[tauri::command]
+pub fn set_mod_inis_enabled(path: String, enabled: bool) -> Result<Vec<ToggleResult>, String> {
+    let entries = std::fs::read_dir(&path).map_err(|e| e.to_string())?;
+    Ok(entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.is_file() && is_ini(p))
+        .map(|p| toggle_result(p.to_string_lossy().to_string(), toggle_path(&p, enabled)))
+        .collect())
+}
+
+/// List the mods installed for a game
+#[tauri::command]
+pub fn list_mods(game: String) -> Result<Vec<InstalledMod>, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    Ok(scan_mods(&root))
+}
@@ -0,0 +1,435 @@
+use crate::app_config::{app_path, load_config, mods_root};
+use crate::category_manager::folder_name;
+use crate::cold_storage::{read_stub, COLD_STUB_FILE};
+use crate::fs_utils::{copy_dir_all, hash_file, move_path, walk_files};
+use crate::manifest::{
+    now_secs, read_manifest, write_manifest, ModManifest, QueuedDownload, MANIFEST_FILE,
+};
+use crate::mod_manager::{disabled_name, scan_mods, unique_path, InstalledMod};
+use crate::profile_manager::{find_profile, upsert_profile, Profile};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Component, Path, PathBuf};
+use tauri::Emitter;
+
+/// Index stored at the root of every mod-pack archive
+const MODPACK_INDEX: &str = "modpack.json";
+const MODPACK_MODS_DIR: &str = "mods";
+const MODPACK_FORMAT: u32 = 1;
+/// Event the frontend download queue takes mods to fetch from
+const QUEUE_EVENT: &str = "queue-downloads";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ModpackMod {
+    /// `mod_manager::mod_key` on the exporting machine, as used by the bundled profile
+    pub key: String,
+    pub name: String,
+    pub category: String,
+    pub enabled: bool,
+    /// Folder inside the archive holding the mod; empty in links-only packs
+    pub folder: String,
+    pub manifest: ModManifest,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ModpackIndex {
+    pub format: u32,
+    pub name: String,
+    pub game: String,
+    pub created: u64,
+    pub links_only: bool,
+    pub profile: Option<Profile>,
+    pub mods: Vec<ModpackMod>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModpackExport {
+    pub path: String,
+    pub exported: Vec<String>,
+    /// Mods left out of a links-only pack because they have no GameBanana source
+    pub skipped: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ReinstallReport {
+    pub queued: Vec<QueuedDownload>,
+    /// Mods already installed with the same file and contents
+    pub present: Vec<String>,
+    /// Manifests that cannot be reinstalled, with the reason
+    pub skipped: Vec<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModpackImport {
+    pub name: String,
+    pub game: String,
+    /// Mod folders copied out of the pack
+    pub installed: Vec<String>,
+    /// Mods to fetch from GameBanana, for the download queue
+    pub queued: Vec<QueuedDownload>,
+    pub skipped: Vec<String>,
+    pub profile: Option<String>,
+}
+
+/// Bundle mods of a game, and optionally one of its profiles, into a 7z mod-pack.
+/// With `links_only` the pack holds just the manifests, so importing downloads the mods again.
+#[tauri::command]
+pub async fn export_modpack(
+    app_handle: tauri::AppHandle,
+    game: String,
+    paths: Vec<String>,
+    dest: String,
+    name: Option<String>,
+    profile: Option<String>,
+    links_only: bool,
+) -> Result<ModpackExport, String> {
+    let root = mods_root(&game)?;
+    let wanted: BTreeSet<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    let staging = app_path("downloads").join(format!("modpack_{}", now_secs()));
+    let mut index = ModpackIndex {
+        format: MODPACK_FORMAT,
+        name: name.unwrap_or_default(),
+        game: game.clone(),
+        created: now_secs(),
+        links_only,
+        profile: match profile {
+            Some(profile) => Some(find_profile(&game, &profile)?),
+            None => None,
+        },
+        mods: Vec::new(),
+    };
+    let mut report = ModpackExport {
+        path: dest.clone(),
+        ..Default::default()
+    };
+
+    let result = async {
+        for installed in scan_mods(&root) {
+            let mod_dir = PathBuf::from(&installed.path);
+            if !wanted.contains(&mod_dir) {
+                continue;
+            }
+            let mut manifest = installed.manifest.clone().unwrap_or_default();
+            if manifest.name.is_empty() {
+                manifest.name = installed.name.clone();
+            }
+            manifest.game = game.clone();
+            if links_only && (manifest.mod_id().is_none() || manifest.file.is_empty()) {
+                report.skipped.push(installed.path.clone());
+                continue;
+            }
+            let folder = if links_only {
+                String::new()
+            } else {
+                let folder = format!("{}/{}", MODPACK_MODS_DIR, index.mods.len());
+                let packed = staging.join(&folder);
+                // A stub's archive path only exists on this machine, so archived mods go in unpacked
+                if let Some(stub) = read_stub(&mod_dir) {
+                    crate::decompress_file(
+                        app_handle.clone(),
+                        &stub.archive,
+                        &packed.to_string_lossy(),
+                    )
+                    .await
+                    .map_err(|e| format!("Failed to unpack archived {}: {}", installed.path, e))?;
+                }
+                // Copied over the unpacked files: the stub's manifest is newer than the archived one
+                copy_dir_all(&mod_dir, &packed)?;
+                let _ = std::fs::remove_file(packed.join(COLD_STUB_FILE));
+                folder
+            };
+            index.mods.push(ModpackMod {
+                key: installed.key.clone(),
+                name: installed.name.clone(),
+                category: installed.category_name(),
+                enabled: installed.enabled,
+                folder,
+                manifest,
+            });
+            report.exported.push(installed.path);
+        }
+        std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+        let data = serde_json::to_string_pretty(&index).map_err(|e| e.to_string())?;
+        std::fs::write(staging.join(MODPACK_INDEX), data)
+            .map_err(|e| format!("Failed to write mod-pack index: {}", e))?;
+        let _ = std::fs::remove_file(&dest);
+        crate::compress_dir(&app_handle, &staging, Path::new(&dest)).await
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&staging);
+    result?;
+    println!(
+        "Exported {} mods of {} to {}{}",
+        report.exported.len(),
+        game,
+        dest,
+        if links_only { " (links only)" } else { "" }
+    );
+    Ok(report)
+}
+
+/// Hand downloads to the frontend queue, which installs them through the regular pipeline
+fn enqueue(app_handle: &tauri::AppHandle, items: &[QueuedDownload]) {
+    if items.is_empty() {
+        return;
+    }
+    if let Err(e) = app_handle.emit(QUEUE_EVENT, items) {
+        println!("Failed to queue {} downloads: {}", items.len(), e);
+    }
+}
+
+/// A path taken from a pack index, refused unless it stays below the folder it is joined to
+fn pack_path(value: &str) -> Result<PathBuf, String> {
+    let path = Path::new(value);
+    let inside = !path.has_root() && path.components().all(|c| matches!(c, Component::Normal(_)));
+    if !inside {
+        return Err(format!(
+            "Mod-pack refers to a path outside the pack: '{}'",
+            value
+        ));
+    }
+    Ok(path.to_path_buf())
+}
+
+/// Check the parts of a pack entry that become paths, before anything is extracted or moved
+fn validate_entry(entry: &mut ModpackMod) -> Result<(), String> {
+    if !entry.folder.is_empty() {
+        pack_path(&entry.folder)?;
+    }
+    pack_path(&entry.name)?;
+    entry.name = folder_name(&entry.name)?;
+    if !entry.category.is_empty() {
+        pack_path(&entry.category)?;
+        entry.category = folder_name(&entry.category)?;
+    }
+    Ok(())
+}
+
+/// Recreate a mod-pack: bundled mods are copied into the game's Mods folder,
+/// links-only entries are sent to the download queue.
+#[tauri::command]
+pub async fn import_modpack(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<ModpackImport, String> {
+    let staging = app_path("downloads").join(format!("modpack_import_{}", now_secs()));
+    let result = async {
+        crate::decompress_file(app_handle.clone(), &path, &staging.to_string_lossy()).await?;
+        let data = std::fs::read_to_string(staging.join(MODPACK_INDEX))
+            .map_err(|_| format!("{} is not a mod-pack", path))?;
+        let mut index: ModpackIndex =
+            serde_json::from_str(&data).map_err(|e| format!("Invalid mod-pack index: {}", e))?;
+        for entry in &mut index.mods {
+            validate_entry(entry)?;
+        }
+        let root = mods_root(&index.game)?;
+        let categorized = load_config().categorized.unwrap_or(true);
+        let mut report = ModpackImport {
+            name: index.name.clone(),
+            game: index.game.clone(),
+            ..Default::default()
+        };
+
+        for entry in &index.mods {
+            let folder = staging.join(&entry.folder);
+            if entry.folder.is_empty() || !folder.is_dir() {
+                if entry.manifest.file.is_empty() {
+                    report.skipped.push(entry.name.clone());
+                } else {
+                    let mut manifest = entry.manifest.clone();
+                    manifest.game = index.game.clone();
+                    manifest.category = entry.category.clone();
+                    report.queued.push(manifest.to_download());
+                }
+                continue;
+            }
+            let parent = if categorized && !entry.category.is_empty() {
+                root.join(&entry.category)
+            } else {
+                root.clone()
+            };
+            std::fs::create_dir_all(&parent).map_err(|e| e.to_string())?;
+            let name = if entry.enabled {
+                entry.name.clone()
+            } else {
+                disabled_name(&entry.name)
+            };
+            let target = unique_path(&parent, &name, false);
+            move_path(&folder, &target)?;
+            if let Some(mut manifest) = read_manifest(&target) {
+                manifest.game = index.game.clone();
+                manifest.enabled = entry.enabled;
+                write_manifest(&target, &manifest)?;
+            }
+            report.installed.push(target.to_string_lossy().to_string());
+        }
+
+        if let Some(profile) = index.profile.clone() {
+            report.profile = Some(upsert_profile(&index.game, profile)?.name);
+        }
+        Ok::<_, String>(report)
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&staging);
+    let report = result?;
+    enqueue(&app_handle, &report.queued);
+    println!(
+        "Imported mod-pack {}: {} installed, {} queued, {} skipped",
+        path,
+        report.installed.len(),
+        report.queued.len(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+/// Manifests from a backup folder, a manifest list, a mod-pack index or a single manifest
+fn collect_manifests(path: &Path) -> Result<Vec<ModManifest>, String> {
+    if path.is_dir() {
+        return Ok(walk_files(path)
+            .into_iter()
+            .filter(|f| f.file_name().is_some_and(|n| n == MANIFEST_FILE))
+            .filter_map(|f| f.parent().and_then(read_manifest))
+            .collect());
+    }
+    let data =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read {:?}: {}", path, e))?;
+    if let Ok(manifests) = serde_json::from_str::<Vec<ModManifest>>(&data) {
+        return Ok(manifests);
+    }
+    if let Ok(index) = serde_json::from_str::<ModpackIndex>(&data) {
+        if !index.mods.is_empty() {
+            return Ok(index
+                .mods
+                .into_iter()
+                .map(|m| ModManifest {
+                    game: index.game.clone(),
+                    category: m.category,
+                    ..m.manifest
+                })
+                .collect());
+        }
+    }
+    serde_json::from_str::<ModManifest>(&data)
+        .map(|m| vec![m])
+        .map_err(|e| format!("{:?} holds no mod manifests: {}", path, e))
+}
+
+/// Whether an installed mod already matches a manifest: same file, and every recorded file unchanged
+fn is_present(installed: &InstalledMod, wanted: &ModManifest) -> bool {
+    let Some(manifest) = &installed.manifest else {
+        return false;
+    };
+    if manifest.file != wanted.file || manifest.mod_id() != wanted.mod_id() {
+        return false;
+    }
+    let mod_dir = Path::new(&installed.path);
+    wanted
+        .files
+        .iter()
+        .all(|(rel, hash)| hash_file(&mod_dir.join(rel)).is_ok_and(|current| current == *hash))
+}
+
+/// Queue downloads for every manifest in a backup or exported mod list, skipping mods already installed
+#[tauri::command]
+pub fn reinstall_from_manifests(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<ReinstallReport, String> {
+    let manifests = collect_manifests(Path::new(&path))?;
+    let mut report = ReinstallReport::default();
+    let mut installed_by_game: BTreeMap<String, Vec<InstalledMod>> = BTreeMap::new();
+    let mut seen = BTreeSet::new();
+    for manifest in manifests {
+        let label = if manifest.name.is_empty() {
+            manifest.source.clone()
+        } else {
+            manifest.name.clone()
+        };
+        if manifest.file.is_empty() || manifest.mod_id().is_none() {
+            report
+                .skipped
+                .push(format!("{}: no GameBanana download link", label));
+            continue;
+        }
+        if !seen.insert((manifest.game.clone(), manifest.file.clone())) {
+            continue;
+        }
+        let installed = match installed_by_game.get(&manifest.game) {
+            Some(installed) => installed,
+            None => {
+                let root = match mods_root(&manifest.game) {
+                    Ok(root) => root,
+                    Err(e) => {
+                        report.skipped.push(format!("{}: {}", label, e));
+                        continue;
+                    }
+                };
+                installed_by_game
+                    .entry(manifest.game.clone())
+                    .or_insert_with(|| scan_mods(&root))
+            }
+        };
+        match installed.iter().find(|m| is_present(m, &manifest)) {
+            Some(existing) => report.present.push(existing.path.clone()),
+            None => report.queued.push(manifest.to_download()),
+        }
+    }
+    enqueue(&app_handle, &report.queued);
+    println!(
+        "Reinstall from {}: {} queued, {} already present, {} skipped",
+        path,
+        report.queued.len(),
+        report.present.len(),
+        report.skipped.len()
+    );
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(folder: &str, category: &str, name: &str) -> ModpackMod {
+        ModpackMod {
+            folder: folder.to_string(),
+            category: category.to_string(),
+            name: name.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn accepts_plain_entries() {
+        let mut valid = entry("mods/0", "Characters", "Ayaka&Ei ");
+        validate_entry(&mut valid).unwrap();
+        assert_eq!(valid.name, "Ayaka&Ei");
+        validate_entry(&mut entry("", "", "Links only")).unwrap();
+    }
+
+    #[test]
+    fn rejects_paths_leaving_the_pack() {
+        for (folder, category, name) in [
+            ("../../Users", "", "Mod"),
+            ("/etc", "", "Mod"),
+            ("mods/0", "..", "Mod"),
+            ("mods/0", "a/b", "Mod"),
+            ("mods/0", "", ".."),
+            ("mods/0", "", "/tmp/Mod"),
+            ("mods/0", "", "Mod\\..\\..\\x"),
+        ] {
+            assert!(
+                validate_entry(&mut entry(folder, category, name)).is_err(),
+                "{} {} {}",
+                folder,
+                category,
+                name
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::app_config::mods_root;
+use crate::dds_decoder::decode_dds;
+use crate::fs_utils::walk_files;
+use crate::gallery_manager::is_main_preview;
+use crate::ini_parser::load_mod_ini;
+use crate::mod_manager::{active_inis, relative_path, scan_mods};
+use std::path::{Path, PathBuf};
+
+/// Longest side of generated previews
+const PREVIEW_SIZE: u32 = 512;
+const PREVIEW_FILE: &str = "preview.png";
+/// Texture names that point at the base color of a model
+const DIFFUSE_HINTS: &[&str] = &["diffuse", "albedo", "basecolor", "base_color", "color"];
+/// Textures that are never worth previewing
+const NON_DIFFUSE_HINTS: &[&str] = &[
+    "normal",
+    "lightmap",
+    "materialmap",
+    "material",
+    "shadowramp",
+    "ramp",
+    "mask",
+    "metal",
+    "emission",
+    "glow",
+];
+
+/// Existing `preview.<ext>` file of a mod folder; further gallery images don't count
+fn find_preview(mod_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(mod_dir)
+        .ok()?
+        .flatten()
+        .find_map(|entry| {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            (path.is_file() && is_main_preview(&name)).then_some(path)
+        })
+}
+
+fn is_dds(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("dds"))
+}
+
+/// Rank a texture by how likely it is the diffuse map: hint matches first, then file size
+fn diffuse_score(label: &str, path: &Path) -> Option<(usize, u64)> {
+    let label = label.to_ascii_lowercase();
+    if NON_DIFFUSE_HINTS.iter().any(|hint| label.contains(hint)) {
+        return None;
+    }
+    let rank = DIFFUSE_HINTS
+        .iter()
+        .position(|hint| label.contains(hint))
+        .map(|i| DIFFUSE_HINTS.len() - i)
+        .unwrap_or(0);
+    let size = path.metadata().map(|m| m.len()).unwrap_or(0);
+    Some((rank, size))
+}
+
+/// Textures referenced by the mod's `Resource` sections, best diffuse candidate first.
+/// Falls back to any DDS in the folder when the inis reference none.
+fn diffuse_candidates(mod_dir: &Path) -> Vec<PathBuf> {
+    let mut scored: Vec<((usize, u64), PathBuf)> = Vec::new();
+    for ini in active_inis(mod_dir) {
+        let (Ok(model), Some(ini_dir)) = (load_mod_ini(&ini), ini.parent()) else {
+            continue;
+        };
+        for resource in model.resources {
+            let Some(filename) = resource.filename.filter(|_| resource.exists == Some(true)) else {
+                continue;
+            };
+            let path = ini_dir.join(filename.replace('\\', "/"));
+            if !is_dds(&path) || scored.iter().any(|(_, p)| *p == path) {
+                continue;
+            }
+            let label = format!("{} {}", resource.section, filename);
+            if let Some(score) = diffuse_score(&label, &path) {
+                scored.push((score, path));
+            }
+        }
+    }
+    if scored.is_empty() {
+        for path in walk_files(mod_dir).into_iter().filter(|p| is_dds(p)) {
+            if let Some(score) = diffuse_score(&relative_path(mod_dir, &path), &path) {
+                scored.push((score, path));
+            }
+        }
+    }
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Decode a texture and write it, downscaled and opaque, as the mod's preview
+fn write_preview(texture: &Path, dest: &Path) -> Result<(), String> {
+    let data =
+        std::fs::read(texture).map_err(|e| format!("Failed to read {:?}: {}", texture, e))?;
+    let mut decoded = decode_dds(&data, PREVIEW_SIZE)?;
+    // Diffuse alpha usually holds masks rather than transparency
+    for pixel in decoded.pixels.chunks_exact_mut(4) {
+        pixel[3] = 255;
+    }
+    let image = image::RgbaImage::from_raw(decoded.width, decoded.height, decoded.pixels)
+        .ok_or_else(|| format!("Invalid texture size in {:?}", texture))?;
+    let preview = image::DynamicImage::ImageRgba8(image).thumbnail(PREVIEW_SIZE, PREVIEW_SIZE);
+    preview
+        .to_rgb8()
+        .save(dest)
+        .map_err(|e| format!("Failed to write preview {:?}: {}", dest, e))
+}
+
+/// Generate a preview from the mod's textures unless it already has one
+fn generate_preview(mod_dir: &Path, force: bool) -> Result<Option<PathBuf>, String> {
+    if !mod_dir.is_dir() {
+        return Err(format!("Mod folder not found: {:?}", mod_dir));
+    }
+    let existing = find_preview(mod_dir);
+    if !force && existing.is_some() {
+        return Ok(None);
+    }
+    let dest = mod_dir.join(PREVIEW_FILE);
+    for texture in diffuse_candidates(mod_dir) {
+        match write_preview(&texture, &dest) {
+            Ok(()) => {
+                if let Some(old) = existing.filter(|old| *old != dest) {
+                    let _ = std::fs::remove_file(old);
+                }
+                println!("Generated preview for {:?} from {:?}", mod_dir, texture);
+                return Ok(Some(dest));
+            }
+            Err(e) => println!("Skipping texture {:?}: {}", texture, e),
+        }
+    }
+    println!("No usable texture found for a preview of {:?}", mod_dir);
+    Ok(None)
+}
+
+/// Write `preview.png` from the mod's diffuse texture; `force` replaces an existing preview.
+/// Returns the written file, or nothing when the mod has a preview or no usable texture.
+#[tauri::command]
+pub fn generate_mod_preview(path: String, force: bool) -> Result<Option<String>, String> {
+    Ok(generate_preview(Path::new(&path), force)?.map(|p| p.to_string_lossy().to_string()))
+}
+
+/// Generate previews for every mod of a game that has none
+#[tauri::command]
+pub fn generate_missing_previews(game: String) -> Result<Vec<String>, String> {
+    let root = mods_root(&game)?;
+    let mut generated = Vec::new();
+    for installed in scan_mods(&root) {
+        let mod_dir = Path::new(&installed.path);
+        if installed.archived {
+            continue;
+        }
+        match generate_preview(mod_dir, false) {
+            Ok(Some(preview)) => generated.push(preview.to_string_lossy().to_string()),
+            Ok(None) => {}
+            Err(e) => println!("{}", e),
+        }
+    }
+    println!("Generated {} previews for {}", generated.len(), game);
+    Ok(generated)
+}
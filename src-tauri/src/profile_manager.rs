@@ -0,0 +1,269 @@
+use crate::app_config::{app_path, mods_root};
+use crate::library_store::{create_dir_link, is_link, library_mods, remove_link};
+use crate::manifest::now_secs;
+use crate::mod_manager::{scan_mods, toggle_path};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// A named set of enabled mods for one game, keyed by `mod_manager::mod_key`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Profile {
+    pub name: String,
+    pub mods: BTreeSet<String>,
+    pub created: u64,
+    pub updated: u64,
+}
+
+/// Mods whose state changed (or would change) when applying a profile
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileDiff {
+    pub enabled: Vec<String>,
+    pub disabled: Vec<String>,
+    pub missing: Vec<String>,
+}
+
+type ProfileStore = HashMap<String, Vec<Profile>>;
+
+fn load_profiles() -> ProfileStore {
+    std::fs::read_to_string(app_path(PROFILES_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_profiles(store: &ProfileStore) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(store).map_err(|e| e.to_string())?;
+    let path = app_path(PROFILES_FILE);
+    let tmp = path.with_extension("json.tmp");
+    std::fs::write(&tmp, data).map_err(|e| format!("Failed to write profiles: {}", e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to save profiles: {}", e))
+}
+
+/// Get a profile of a game by name
+pub fn find_profile(game: &str, name: &str) -> Result<Profile, String> {
+    load_profiles()
+        .get(game)
+        .and_then(|profiles| profiles.iter().find(|p| p.name == name))
+        .cloned()
+        .ok_or_else(|| format!("Profile '{}' not found for game '{}'", name, game))
+}
+
+/// Point profiles at a mod's new key after it was moved or renamed
+pub fn rename_profile_key(game: &str, old_key: &str, new_key: &str) -> Result<usize, String> {
+    if old_key == new_key {
+        return Ok(0);
+    }
+    let mut store = load_profiles();
+    let mut changed = 0;
+    for profile in store.get_mut(game).into_iter().flatten() {
+        if profile.mods.remove(old_key) {
+            profile.mods.insert(new_key.to_string());
+            changed += 1;
+        }
+    }
+    if changed > 0 {
+        save_profiles(&store)?;
+    }
+    Ok(changed)
+}
+
+/// Store a profile, replacing any existing one with the same name
+pub fn upsert_profile(game: &str, mut profile: Profile) -> Result<Profile, String> {
+    let mut store = load_profiles();
+    let profiles = store.entry(game.to_string()).or_default();
+    profile.updated = now_secs();
+    match profiles.iter_mut().find(|p| p.name == profile.name) {
+        Some(existing) => {
+            profile.created = existing.created;
+            *existing = profile.clone();
+        }
+        None => {
+            profile.created = profile.updated;
+            profiles.push(profile.clone());
+        }
+    }
+    save_profiles(&store)?;
+    Ok(profile)
+}
+
+/// One reversible change made while applying a profile
+enum Step {
+    /// Rename a mod folder to enable or disable it
+    Toggle(PathBuf, bool),
+    /// Link a library folder into the Mods folder: (library folder, link)
+    Link(PathBuf, PathBuf),
+    Unlink(PathBuf, PathBuf),
+}
+
+/// Apply a step, returning the step that undoes it
+fn apply_step(step: Step) -> Result<Step, String> {
+    match step {
+        Step::Toggle(path, enable) => {
+            toggle_path(&path, enable).map(|new| Step::Toggle(new, !enable))
+        }
+        Step::Link(target, link) => {
+            create_dir_link(&target, &link).map(|_| Step::Unlink(target, link))
+        }
+        Step::Unlink(target, link) => remove_link(&link).map(|_| Step::Link(target, link)),
+    }
+}
+
+/// Work out which mod folders need renaming and which library mods need (un)linking to match `wanted`
+fn plan_profile(game: &str, root: &Path, wanted: &BTreeSet<String>) -> (Vec<Step>, ProfileDiff) {
+    let mut plan = Vec::new();
+    let mut diff = ProfileDiff::default();
+    let mut found = BTreeSet::new();
+    for installed in scan_mods(root) {
+        // Library links are switched by relinking below, never renamed
+        if is_link(Path::new(&installed.path)) {
+            continue;
+        }
+        let enable = wanted.contains(&installed.key);
+        if enable {
+            found.insert(installed.key.clone());
+        }
+        if enable == installed.enabled {
+            continue;
+        }
+        if enable {
+            diff.enabled.push(installed.key);
+        } else {
+            diff.disabled.push(installed.key);
+        }
+        plan.push(Step::Toggle(PathBuf::from(installed.path), enable));
+    }
+    for stored in library_mods(game, root) {
+        let enable = wanted.contains(&stored.key);
+        if enable {
+            found.insert(stored.key.clone());
+        }
+        if enable == stored.enabled {
+            continue;
+        }
+        let target = PathBuf::from(&stored.path);
+        if let Some(link) = stored.link.as_ref().map(PathBuf::from) {
+            plan.push(Step::Unlink(target.clone(), link));
+        }
+        if enable {
+            plan.push(Step::Link(target, root.join(&stored.key)));
+            diff.enabled.push(stored.key);
+        } else {
+            diff.disabled.push(stored.key);
+        }
+    }
+    diff.missing = wanted.difference(&found).cloned().collect();
+    (plan, diff)
+}
+
+/// Apply a plan, undoing every step already done if one of them fails
+fn execute_plan(plan: Vec<Step>) -> Result<(), String> {
+    let mut undo: Vec<Step> = Vec::new();
+    for step in plan {
+        match apply_step(step) {
+            Ok(inverse) => undo.push(inverse),
+            Err(e) => {
+                println!(
+                    "Profile apply failed, rolling back {} changes: {}",
+                    undo.len(),
+                    e
+                );
+                for step in undo.into_iter().rev() {
+                    if let Err(e) = apply_step(step) {
+                        println!("Rollback step failed: {}", e);
+                    }
+                }
+                return Err(e);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Switch a game's Mods folder to a set of enabled mods in one step, restoring archived ones first
+pub async fn apply_mod_set(
+    app_handle: &tauri::AppHandle,
+    game: &str,
+    wanted: &BTreeSet<String>,
+    dry_run: bool,
+) -> Result<ProfileDiff, String> {
+    let root = mods_root(game)?;
+    let (plan, diff) = plan_profile(game, &root, wanted);
+    if !dry_run {
+        for step in &plan {
+            match step {
+                Step::Toggle(path, true) | Step::Link(path, _) => {
+                    crate::cold_storage::ensure_restored(app_handle, path).await?
+                }
+                _ => {}
+            }
+        }
+        execute_plan(plan)?;
+    }
+    Ok(diff)
+}
+
+#[tauri::command]
+pub fn list_profiles(game: String) -> Vec<Profile> {
+    load_profiles().remove(&game).unwrap_or_default()
+}
+
+/// Save the mods currently enabled for a game as a profile
+#[tauri::command]
+pub fn save_profile(game: String, name: String) -> Result<Profile, String> {
+    if name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    let root = mods_root(&game)?;
+    let mods = scan_mods(&root)
+        .into_iter()
+        .filter(|m| m.enabled)
+        .map(|m| m.key)
+        .collect();
+    upsert_profile(
+        &game,
+        Profile {
+            name: name.trim().to_string(),
+            mods,
+            ..Default::default()
+        },
+    )
+}
+
+#[tauri::command]
+pub fn delete_profile(game: String, name: String) -> Result<(), String> {
+    let mut store = load_profiles();
+    let profiles = store.entry(game.clone()).or_default();
+    let before = profiles.len();
+    profiles.retain(|p| p.name != name);
+    if profiles.len() == before {
+        return Err(format!("Profile '{}' not found for game '{}'", name, game));
+    }
+    save_profiles(&store)
+}
+
+/// Enable exactly the mods of a profile; with `dry_run` only report the changes
+#[tauri::command]
+pub async fn apply_profile(
+    app_handle: tauri::AppHandle,
+    game: String,
+    name: String,
+    dry_run: bool,
+) -> Result<ProfileDiff, String> {
+    let profile = find_profile(&game, &name)?;
+    let diff = apply_mod_set(&app_handle, &game, &profile.mods, dry_run).await?;
+    println!(
+        "Profile '{}' for {}: {} enabled, {} disabled, {} missing{}",
+        name,
+        game,
+        diff.enabled.len(),
+        diff.disabled.len(),
+        diff.missing.len(),
+        if dry_run { " (dry run)" } else { "" }
+    );
+    Ok(diff)
+}
@@ -0,0 +1,207 @@
+use crate::cold_storage::is_archived;
+use crate::fs_utils::{content_root, walk_files};
+use crate::manifest::is_imi_file;
+use crate::mod_manager::relative_path;
+use encoding_rs::{Encoding, EUC_KR, GBK, SHIFT_JIS, UTF_16BE, UTF_16LE, UTF_8, WINDOWS_1252};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+const README_EXTS: &[&str] = &["txt", "md", "nfo"];
+/// Name fragments of files worth reading before using a mod, and what they hold
+const README_KINDS: &[(&str, &str)] = &[
+    ("readme", "readme"),
+    ("read me", "readme"),
+    ("read_me", "readme"),
+    ("instruction", "instructions"),
+    ("install", "instructions"),
+    ("how to", "instructions"),
+    ("howto", "instructions"),
+    ("keybind", "instructions"),
+    ("hotkey", "instructions"),
+    ("controls", "instructions"),
+    ("credit", "credits"),
+    ("thanks", "credits"),
+];
+/// Anything bigger is not a readme
+const MAX_README_SIZE: u64 = 256 * 1024;
+/// Share of non-ASCII bytes from which legacy CJK code pages are tried before Windows-1252
+const CJK_NON_ASCII_RATIO: f32 = 0.2;
+
+/// A readme-like file of a mod, decoded to UTF-8
+#[derive(Serialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModReadme {
+    /// Path relative to the mod folder
+    pub file: String,
+    /// `readme`, `instructions` or `credits`
+    pub kind: String,
+    /// Encoding the file was written in
+    pub encoding: String,
+    pub content: String,
+}
+
+fn readme_kind(path: &Path) -> Option<&'static str> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    if !README_EXTS.contains(&ext.as_str()) {
+        return None;
+    }
+    let stem = path.file_stem()?.to_str()?.to_ascii_lowercase();
+    README_KINDS
+        .iter()
+        .find(|(hint, _)| stem.contains(hint))
+        .map(|(_, kind)| *kind)
+}
+
+/// UTF-16 written without a BOM shows up as every other byte being zero
+fn utf16_without_bom(data: &[u8]) -> Option<&'static Encoding> {
+    if data.len() < 2 || !data.len().is_multiple_of(2) {
+        return None;
+    }
+    let pairs = data.len() / 2;
+    let even = data.iter().step_by(2).filter(|b| **b == 0).count();
+    let odd = data.iter().skip(1).step_by(2).filter(|b| **b == 0).count();
+    if odd * 5 > pairs * 2 && even == 0 {
+        Some(UTF_16LE)
+    } else if even * 5 > pairs * 2 && odd == 0 {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+/// Decode text by its BOM, as UTF-8, or from the legacy code pages mod authors commonly save with
+pub fn decode_text(data: &[u8]) -> (String, &'static Encoding) {
+    if let Some((encoding, bom)) = Encoding::for_bom(data) {
+        let (text, _) = encoding.decode_without_bom_handling(&data[bom..]);
+        return (text.into_owned(), encoding);
+    }
+    // Checked first: ASCII in UTF-16 is also valid UTF-8, just full of NULs
+    if let Some(encoding) = utf16_without_bom(data) {
+        let (text, _) = encoding.decode_without_bom_handling(data);
+        return (text.into_owned(), encoding);
+    }
+    if let Ok(text) = std::str::from_utf8(data) {
+        return (text.to_string(), UTF_8);
+    }
+    // Western text is mostly ASCII, so CJK code pages only get a try when most of it isn't
+    let non_ascii = data.iter().filter(|b| !b.is_ascii()).count();
+    if non_ascii as f32 >= data.len() as f32 * CJK_NON_ASCII_RATIO {
+        for encoding in [GBK, SHIFT_JIS, EUC_KR] {
+            if let Some(text) = encoding.decode_without_bom_handling_and_without_replacement(data) {
+                return (text.into_owned(), encoding);
+            }
+        }
+    }
+    let (text, _) = WINDOWS_1252.decode_without_bom_handling(data);
+    (text.into_owned(), WINDOWS_1252)
+}
+
+/// Readme-like files below `dir`, shallowest first
+fn find_readmes(dir: &Path) -> Vec<PathBuf> {
+    let mut found: Vec<PathBuf> = walk_files(dir)
+        .into_iter()
+        .filter(|path| {
+            readme_kind(path).is_some()
+                && !is_imi_file(&relative_path(dir, path))
+                && path.metadata().is_ok_and(|m| m.len() <= MAX_README_SIZE)
+        })
+        .collect();
+    found.sort_by_key(|path| {
+        (
+            path.components().count(),
+            path.to_string_lossy().to_lowercase(),
+        )
+    });
+    found
+}
+
+/// Decode every readme of a mod folder; `normalize` rewrites files not stored as plain UTF-8
+pub fn collect_readmes(mod_dir: &Path, normalize: bool) -> Vec<ModReadme> {
+    // Paths are relative to where the files end up once wrapper folders are flattened
+    let root = content_root(mod_dir);
+    let mut readmes = Vec::new();
+    for path in find_readmes(mod_dir) {
+        let Ok(data) = std::fs::read(&path) else {
+            continue;
+        };
+        let (content, encoding) = decode_text(&data);
+        if normalize && (encoding != UTF_8 || data.starts_with(b"\xEF\xBB\xBF")) {
+            match std::fs::write(&path, content.as_bytes()) {
+                Ok(()) => println!("Converted {:?} from {} to UTF-8", path, encoding.name()),
+                Err(e) => println!("Failed to convert {:?} to UTF-8: {}", path, e),
+            }
+        }
+        let file = if path.starts_with(&root) {
+            relative_path(&root, &path)
+        } else {
+            relative_path(mod_dir, &path)
+        };
+        readmes.push(ModReadme {
+            file,
+            kind: readme_kind(&path).unwrap_or("readme").to_string(),
+            encoding: encoding.name().to_string(),
+            content,
+        });
+    }
+    readmes
+}
+
+/// Readme, instruction and credit files of an installed mod
+#[tauri::command]
+pub fn get_mod_readme(path: String) -> Result<Vec<ModReadme>, String> {
+    let mod_dir = Path::new(&path);
+    if !mod_dir.is_dir() {
+        return Err(format!("Mod folder not found: {}", path));
+    }
+    if is_archived(mod_dir) {
+        return Err(format!(
+            "{} is in cold storage, restore it to read its files",
+            path
+        ));
+    }
+    Ok(collect_readmes(mod_dir, false))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utf16le(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    #[test]
+    fn bom_picks_the_encoding_and_is_dropped() {
+        assert_eq!(
+            decode_text(b"\xEF\xBB\xBFPress F5"),
+            ("Press F5".to_string(), UTF_8)
+        );
+        let mut data = vec![0xFF, 0xFE];
+        data.extend(utf16le("Press F5"));
+        assert_eq!(decode_text(&data), ("Press F5".to_string(), UTF_16LE));
+    }
+
+    #[test]
+    fn utf16_without_bom_is_detected() {
+        let data = utf16le("Toggle with the 1 key");
+        assert_eq!(
+            decode_text(&data),
+            ("Toggle with the 1 key".to_string(), UTF_16LE)
+        );
+    }
+
+    #[test]
+    fn gbk_text_is_decoded() {
+        let (data, _, _) = GBK.encode("安装说明：请先备份");
+        assert_eq!(decode_text(&data), ("安装说明：请先备份".to_string(), GBK));
+    }
+
+    #[test]
+    fn western_text_falls_back_to_windows_1252() {
+        let (data, _, _) = WINDOWS_1252.encode("Thanks to Zoë and the café crew");
+        assert_eq!(
+            decode_text(&data),
+            ("Thanks to Zoë and the café crew".to_string(), WINDOWS_1252)
+        );
+    }
+}
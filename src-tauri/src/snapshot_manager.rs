@@ -0,0 +1,394 @@
+use crate::app_config::{app_path, mods_root};
+use crate::cold_storage::{read_stub, COLD_STUB_FILE};
+use crate::fs_utils::{hash_file, replace_dir, walk_files_and_links};
+use crate::library_store::{create_dir_link, resolve_link};
+use crate::manifest::now_secs;
+use crate::mod_manager::relative_path;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+const SNAPSHOTS_DIR: &str = "snapshots";
+const PACKS_DIR: &str = "packs";
+/// Maps every stored blob to the pack archive that holds it
+const BLOB_INDEX_FILE: &str = "blobs.json";
+/// Emit a progress event after at least this many bytes were processed
+const PROGRESS_STEP: u64 = 4 * 1024 * 1024;
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SnapshotFile {
+    pub path: String,
+    pub blob: String,
+    pub size: u64,
+}
+
+/// A folder link in the Mods folder, such as an enabled library mod; only the link is stored
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SnapshotLink {
+    pub path: String,
+    pub target: String,
+}
+
+/// A point-in-time copy of a game's Mods folder; file contents live in shared, deduplicated packs
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Snapshot {
+    pub id: String,
+    pub game: String,
+    pub label: String,
+    pub created: u64,
+    pub root: String,
+    pub size: u64,
+    /// Bytes this snapshot added to the store; everything else was already stored
+    pub stored: u64,
+    pub files: Vec<SnapshotFile>,
+    /// Cold-storage archives of the stubs in the snapshot, by stub folder
+    pub archives: Vec<SnapshotFile>,
+    pub links: Vec<SnapshotLink>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    pub id: String,
+    pub game: String,
+    pub label: String,
+    pub created: u64,
+    pub root: String,
+    pub size: u64,
+    pub stored: u64,
+    pub file_count: usize,
+}
+
+impl From<&Snapshot> for SnapshotInfo {
+    fn from(snapshot: &Snapshot) -> Self {
+        SnapshotInfo {
+            id: snapshot.id.clone(),
+            game: snapshot.game.clone(),
+            label: snapshot.label.clone(),
+            created: snapshot.created,
+            root: snapshot.root.clone(),
+            size: snapshot.size,
+            stored: snapshot.stored,
+            file_count: snapshot.files.len(),
+        }
+    }
+}
+
+fn snapshots_root() -> PathBuf {
+    app_path(SNAPSHOTS_DIR)
+}
+
+fn snapshot_file(id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid snapshot id: {}", id));
+    }
+    Ok(snapshots_root().join(format!("{}.json", id)))
+}
+
+fn load_blob_index() -> BTreeMap<String, String> {
+    std::fs::read_to_string(snapshots_root().join(BLOB_INDEX_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_json<T: Serialize>(path: &Path, value: &T) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    std::fs::write(path, data).map_err(|e| format!("Failed to write {:?}: {}", path, e))
+}
+
+fn load_snapshot(id: &str) -> Result<Snapshot, String> {
+    let data = std::fs::read_to_string(snapshot_file(id)?)
+        .map_err(|_| format!("Snapshot {} not found", id))?;
+    serde_json::from_str(&data).map_err(|e| format!("Snapshot {} is corrupt: {}", id, e))
+}
+
+fn load_snapshots() -> Vec<Snapshot> {
+    let mut snapshots: Vec<Snapshot> = std::fs::read_dir(snapshots_root())
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.extension().is_some_and(|e| e == "json")
+                        && p.file_name().is_some_and(|n| n != BLOB_INDEX_FILE)
+                })
+                .filter_map(|p| std::fs::read_to_string(p).ok())
+                .filter_map(|data| serde_json::from_str(&data).ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort_by_key(|s| std::cmp::Reverse(s.created));
+    snapshots
+}
+
+/// Snapshot the Mods folder at `root`, storing only file contents not already in the store
+async fn take_snapshot(
+    app_handle: &tauri::AppHandle,
+    game: &str,
+    root: &Path,
+    label: String,
+) -> Result<Snapshot, String> {
+    let store = snapshots_root();
+    let created = now_secs();
+    let mut id = format!("{}_{}", game, created);
+    let mut counter = 1;
+    while snapshot_file(&id)?.exists() {
+        id = format!("{}_{}-{}", game, created, counter);
+        counter += 1;
+    }
+    let key = format!("snapshot_{}", id);
+    let staging = app_path("downloads").join(&key);
+    std::fs::create_dir_all(&store).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&staging).map_err(|e| e.to_string())?;
+
+    // Linked library mods are recorded as links, their files belong to the library
+    let (files, links) = walk_files_and_links(root);
+    // Archived mods are only a stub here, so their archives are stored alongside
+    let mut files: Vec<(PathBuf, String, bool)> = files
+        .into_iter()
+        .map(|f| {
+            let path = relative_path(root, &f);
+            (f, path, false)
+        })
+        .collect();
+    let archives: Vec<(PathBuf, String, bool)> = files
+        .iter()
+        .filter(|(f, _, _)| f.file_name().is_some_and(|n| n == COLD_STUB_FILE))
+        .filter_map(|(f, _, _)| {
+            let mod_dir = f.parent()?;
+            let archive = PathBuf::from(read_stub(mod_dir)?.archive);
+            archive
+                .is_file()
+                .then(|| (archive, relative_path(root, mod_dir), true))
+        })
+        .collect();
+    files.extend(archives);
+    let total: u64 = files
+        .iter()
+        .filter_map(|(f, _, _)| f.metadata().ok())
+        .map(|m| m.len())
+        .sum();
+    let mut blob_index = load_blob_index();
+    let mut new_blobs = BTreeSet::new();
+    let mut snapshot = Snapshot {
+        id: id.clone(),
+        game: game.to_string(),
+        label,
+        created,
+        root: root.to_string_lossy().to_string(),
+        links: links
+            .iter()
+            .map(|link| SnapshotLink {
+                path: relative_path(root, link),
+                target: resolve_link(link).to_string_lossy().to_string(),
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let start_time = Instant::now();
+    let mut last_progress = 0;
+    for (file, path, is_archive) in files {
+        let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let hash = hash_file(&file).map_err(|e| format!("Failed to read {:?}: {}", file, e))?;
+        let blob = format!("{}-{:x}", hash, size);
+        if !blob_index.contains_key(&blob) && new_blobs.insert(blob.clone()) {
+            std::fs::copy(&file, staging.join(&blob))
+                .map_err(|e| format!("Failed to stage {:?}: {}", file, e))?;
+            snapshot.stored += size;
+        }
+        snapshot.size += size;
+        let entry = SnapshotFile { path, blob, size };
+        if is_archive {
+            snapshot.archives.push(entry);
+        } else {
+            snapshot.files.push(entry);
+        }
+        if snapshot.size - last_progress >= PROGRESS_STEP {
+            crate::emit_progress(app_handle, &key, snapshot.size, total, start_time);
+            last_progress = snapshot.size;
+        }
+    }
+
+    let result = async {
+        if !new_blobs.is_empty() {
+            let pack = store.join(PACKS_DIR).join(format!("{}.7z", id));
+            crate::compress_dir(app_handle, &staging, &pack).await?;
+            for blob in new_blobs {
+                blob_index.insert(blob, id.clone());
+            }
+            save_json(&store.join(BLOB_INDEX_FILE), &blob_index)?;
+        }
+        save_json(&snapshot_file(&id)?, &snapshot)
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&staging);
+    result?;
+    crate::emit_progress(app_handle, &key, total, total, start_time);
+    println!(
+        "Snapshot {} of {:?}: {} files, {} stored",
+        id,
+        root,
+        snapshot.files.len(),
+        crate::format_bytes(snapshot.stored)
+    );
+    Ok(snapshot)
+}
+
+/// Delete packs none of the remaining snapshots reference
+fn collect_garbage() -> Result<(), String> {
+    let referenced: BTreeSet<String> = load_snapshots()
+        .into_iter()
+        .flat_map(|s| s.files.into_iter().chain(s.archives).map(|f| f.blob))
+        .collect();
+    let mut blob_index = load_blob_index();
+    let live_packs: BTreeSet<String> = blob_index
+        .iter()
+        .filter(|(blob, _)| referenced.contains(*blob))
+        .map(|(_, pack)| pack.clone())
+        .collect();
+    blob_index.retain(|_, pack| live_packs.contains(pack));
+    let packs_dir = snapshots_root().join(PACKS_DIR);
+    if let Ok(entries) = std::fs::read_dir(&packs_dir) {
+        for pack in entries.flatten().map(|e| e.path()) {
+            let id = pack.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            if !live_packs.contains(id) {
+                println!("Removing unused snapshot pack {:?}", pack);
+                let _ = std::fs::remove_file(&pack);
+            }
+        }
+    }
+    save_json(&snapshots_root().join(BLOB_INDEX_FILE), &blob_index)
+}
+
+/// Snapshot a game's Mods folder, including every mod manifest in it
+#[tauri::command]
+pub async fn create_snapshot(
+    app_handle: tauri::AppHandle,
+    game: String,
+    label: Option<String>,
+) -> Result<SnapshotInfo, String> {
+    let root = mods_root(&game)?;
+    let snapshot = take_snapshot(&app_handle, &game, &root, label.unwrap_or_default()).await?;
+    Ok(SnapshotInfo::from(&snapshot))
+}
+
+#[tauri::command]
+pub fn list_snapshots(game: Option<String>) -> Vec<SnapshotInfo> {
+    load_snapshots()
+        .iter()
+        .filter(|s| game.as_ref().is_none_or(|g| *g == s.game))
+        .map(SnapshotInfo::from)
+        .collect()
+}
+
+/// Replace a game's Mods folder with a snapshot; the current state is snapshotted first
+#[tauri::command]
+pub async fn restore_snapshot(
+    app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<SnapshotInfo, String> {
+    let snapshot = load_snapshot(&id)?;
+    let root = mods_root(&snapshot.game).unwrap_or_else(|_| PathBuf::from(&snapshot.root));
+    let blob_index = load_blob_index();
+    let packs: BTreeSet<&String> = snapshot
+        .files
+        .iter()
+        .chain(&snapshot.archives)
+        .map(|f| {
+            blob_index
+                .get(&f.blob)
+                .ok_or_else(|| format!("Snapshot {} is missing data for {}", id, f.path))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let key = format!("snapshot_{}", id);
+    let staging = app_path("downloads").join(format!("{}_restore", key));
+    let blobs = staging.join("blobs");
+    let tree = staging.join("tree");
+    let _ = std::fs::remove_dir_all(&staging);
+    let result = async {
+        let start_time = Instant::now();
+        for (done, pack) in packs.iter().enumerate() {
+            let archive = snapshots_root()
+                .join(PACKS_DIR)
+                .join(format!("{}.7z", pack));
+            crate::decompress_file(
+                app_handle.clone(),
+                &archive.to_string_lossy(),
+                &blobs.to_string_lossy(),
+            )
+            .await?;
+            crate::emit_progress(
+                &app_handle,
+                &key,
+                done as u64 + 1,
+                packs.len() as u64,
+                start_time,
+            );
+        }
+        std::fs::create_dir_all(&tree).map_err(|e| e.to_string())?;
+        for file in &snapshot.files {
+            let target = tree.join(&file.path);
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::copy(blobs.join(&file.blob), &target)
+                .map_err(|e| format!("Failed to restore {}: {}", file.path, e))?;
+        }
+        // Stubs point into cold storage, so bring back archives that have since been removed
+        for archive in &snapshot.archives {
+            let Some(stub) = read_stub(&tree.join(&archive.path)) else {
+                continue;
+            };
+            let target = PathBuf::from(&stub.archive);
+            if target.is_file() {
+                continue;
+            }
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            std::fs::copy(blobs.join(&archive.blob), &target)
+                .map_err(|e| format!("Failed to restore archive of {}: {}", archive.path, e))?;
+        }
+        if root.exists() {
+            take_snapshot(
+                &app_handle,
+                &snapshot.game,
+                &root,
+                format!("Before restoring {}", id),
+            )
+            .await?;
+            replace_dir(&root, &tree)
+        } else {
+            crate::fs_utils::move_path(&tree, &root)
+        }
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&staging);
+    result?;
+    // Links are made in place, as moving the tree across drives would copy what they point to
+    for link in &snapshot.links {
+        let target = PathBuf::from(&link.target);
+        if !target.is_dir() {
+            println!("Skipping link {}: {:?} no longer exists", link.path, target);
+            continue;
+        }
+        if let Err(e) = create_dir_link(&target, &root.join(&link.path)) {
+            println!("{}", e);
+        }
+    }
+    println!("Restored snapshot {} to {:?}", id, root);
+    Ok(SnapshotInfo::from(&snapshot))
+}
+
+#[tauri::command]
+pub fn delete_snapshot(id: String) -> Result<(), String> {
+    std::fs::remove_file(snapshot_file(&id)?)
+        .map_err(|e| format!("Failed to delete snapshot {}: {}", id, e))?;
+    collect_garbage()
+}
@@ -0,0 +1,238 @@
+use crate::app_config::app_path;
+use crate::cold_storage::{read_stub, write_stub};
+use crate::fs_utils::{move_path, path_size};
+use crate::manifest::{now_secs, read_manifest, ModManifest};
+use crate::mod_manager::{enabled_name, unique_path};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const TRASH_DIR: &str = "trash";
+const ENTRY_FILE: &str = "entry.json";
+/// Subfolder of a trash entry that holds the removed mod itself
+const CONTENT_DIR: &str = "content";
+/// Cold-storage archive of a trashed stub, kept next to the content so purging removes both
+const ARCHIVE_FILE: &str = "cold_storage.7z";
+
+/// A mod moved to the trash by `uninstall_mod`
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub id: String,
+    pub name: String,
+    pub origin: String,
+    pub deleted: u64,
+    pub size: u64,
+    pub manifest: Option<ModManifest>,
+    /// Where the cold-storage archive of a trashed stub was taken from
+    pub archive: Option<String>,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PurgeReport {
+    pub removed: Vec<String>,
+    pub freed: u64,
+}
+
+fn trash_root() -> PathBuf {
+    app_path(TRASH_DIR)
+}
+
+fn entry_dir(id: &str) -> Result<PathBuf, String> {
+    if id.is_empty() || id.contains(['/', '\\']) || id.contains("..") {
+        return Err(format!("Invalid trash entry id: {}", id));
+    }
+    Ok(trash_root().join(id))
+}
+
+fn read_entry(dir: &Path) -> Option<TrashEntry> {
+    let data = std::fs::read_to_string(dir.join(ENTRY_FILE)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+fn load_entries() -> Vec<TrashEntry> {
+    let mut entries: Vec<TrashEntry> = std::fs::read_dir(trash_root())
+        .map(|dirs| {
+            dirs.flatten()
+                .filter_map(|dir| read_entry(&dir.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by_key(|e| std::cmp::Reverse(e.deleted));
+    entries
+}
+
+fn remove_entry(id: &str) -> Result<(), String> {
+    let dir = entry_dir(id)?;
+    std::fs::remove_dir_all(&dir).map_err(|e| format!("Failed to delete trash entry {}: {}", id, e))
+}
+
+/// Move a mod (or single file) into the trash
+pub fn trash_path(path: &Path) -> Result<TrashEntry, String> {
+    if !path.exists() {
+        return Err(format!("Nothing to remove at {:?}", path));
+    }
+    let file_name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or_else(|| format!("Invalid path: {:?}", path))?
+        .to_string();
+    let deleted = now_secs();
+    let mut id = deleted.to_string();
+    let mut counter = 1;
+    while trash_root().join(&id).exists() {
+        id = format!("{}-{}", deleted, counter);
+        counter += 1;
+    }
+    let dir = trash_root().join(&id);
+    let archive = read_stub(path)
+        .map(|stub| PathBuf::from(stub.archive))
+        .filter(|archive| archive.is_file());
+    let entry = TrashEntry {
+        id,
+        name: enabled_name(&file_name),
+        origin: path.to_string_lossy().to_string(),
+        deleted,
+        size: path_size(path) + archive.as_deref().map(path_size).unwrap_or(0),
+        manifest: if path.is_dir() {
+            read_manifest(path)
+        } else {
+            None
+        },
+        archive: archive.as_ref().map(|a| a.to_string_lossy().to_string()),
+    };
+    std::fs::create_dir_all(dir.join(CONTENT_DIR))
+        .map_err(|e| format!("Failed to create trash entry: {}", e))?;
+    let data = serde_json::to_string_pretty(&entry).map_err(|e| e.to_string())?;
+    std::fs::write(dir.join(ENTRY_FILE), data)
+        .map_err(|e| format!("Failed to write trash entry: {}", e))?;
+    if let Err(e) = move_path(path, &dir.join(CONTENT_DIR).join(&file_name)) {
+        let _ = std::fs::remove_dir_all(&dir);
+        return Err(e);
+    }
+    if let Some(archive) = archive {
+        if let Err(e) = move_path(&archive, &dir.join(ARCHIVE_FILE)) {
+            println!("Failed to move archive {:?} to trash: {}", archive, e);
+        }
+    }
+    println!("Moved {:?} to trash as {}", path, entry.id);
+    Ok(entry)
+}
+
+/// Uninstall a mod by moving it to the trash
+#[tauri::command]
+pub fn uninstall_mod(path: String) -> Result<TrashEntry, String> {
+    trash_path(Path::new(&path))
+}
+
+#[tauri::command]
+pub fn list_trash() -> Vec<TrashEntry> {
+    load_entries()
+}
+
+/// Put the cold-storage archive of a restored stub back, pointing the stub at where it landed
+fn restore_archive(dir: &Path, entry: &TrashEntry, target: &Path) {
+    let (Some(origin), Some(mut stub)) = (entry.archive.as_ref(), read_stub(target)) else {
+        return;
+    };
+    let trashed = dir.join(ARCHIVE_FILE);
+    if !trashed.is_file() {
+        return;
+    }
+    let origin = PathBuf::from(origin);
+    let (Some(store), Some(name)) = (origin.parent(), origin.file_name().and_then(|n| n.to_str()))
+    else {
+        return;
+    };
+    let archive = unique_path(store, name, true);
+    if let Err(e) = move_path(&trashed, &archive) {
+        println!("Failed to restore archive of {:?}: {}", target, e);
+        return;
+    }
+    stub.archive = archive.to_string_lossy().to_string();
+    if let Err(e) = write_stub(target, &stub) {
+        println!("{}", e);
+    }
+}
+
+/// Move a trashed mod back to where it was removed from and return its new path
+#[tauri::command]
+pub fn restore_from_trash(id: String) -> Result<String, String> {
+    let dir = entry_dir(&id)?;
+    let entry = read_entry(&dir).ok_or_else(|| format!("Trash entry {} not found", id))?;
+    let content = std::fs::read_dir(dir.join(CONTENT_DIR))
+        .ok()
+        .and_then(|mut entries| entries.next())
+        .and_then(|e| e.ok())
+        .map(|e| e.path())
+        .ok_or_else(|| format!("Trash entry {} is empty", id))?;
+    let origin = PathBuf::from(&entry.origin);
+    let parent = origin
+        .parent()
+        .ok_or_else(|| format!("Invalid origin path: {}", entry.origin))?;
+    let name = origin
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&entry.name);
+    let target = unique_path(parent, name, content.is_file());
+    move_path(&content, &target)?;
+    restore_archive(&dir, &entry, &target);
+    if let Err(e) = crate::user_state::migrate_user_state(&origin, &target) {
+        println!("Failed to move saved variables of {:?}: {}", target, e);
+    }
+    remove_entry(&id)?;
+    println!("Restored trash entry {} to {:?}", id, target);
+    Ok(target.to_string_lossy().to_string())
+}
+
+/// Permanently delete one trash entry
+#[tauri::command]
+pub fn delete_from_trash(id: String) -> Result<(), String> {
+    remove_entry(&id)
+}
+
+/// Permanently delete entries older than `max_age_days`, then the oldest ones until the trash fits in `max_size`
+#[tauri::command]
+pub fn purge_trash(
+    max_age_days: Option<u64>,
+    max_size: Option<u64>,
+) -> Result<PurgeReport, String> {
+    let mut report = PurgeReport::default();
+    let mut entries = load_entries();
+    if let Some(days) = max_age_days {
+        let cutoff = now_secs().saturating_sub(days * 24 * 60 * 60);
+        let (expired, kept): (Vec<_>, Vec<_>) =
+            entries.into_iter().partition(|e| e.deleted < cutoff);
+        for entry in expired {
+            remove_entry(&entry.id)?;
+            report.freed += entry.size;
+            report.removed.push(entry.id);
+        }
+        entries = kept;
+    }
+    if let Some(max_size) = max_size {
+        let mut total: u64 = entries.iter().map(|e| e.size).sum();
+        // Entries are newest first, so drop from the back
+        while total > max_size {
+            let Some(entry) = entries.pop() else {
+                break;
+            };
+            remove_entry(&entry.id)?;
+            total -= entry.size;
+            report.freed += entry.size;
+            report.removed.push(entry.id);
+        }
+    }
+    println!(
+        "Purged {} trash entries, freed {} bytes",
+        report.removed.len(),
+        report.freed
+    );
+    Ok(report)
+}
+
+/// Permanently delete everything in the trash
+#[tauri::command]
+pub fn empty_trash() -> Result<PurgeReport, String> {
+    purge_trash(Some(0), Some(0))
+}
@@ -0,0 +1,402 @@
+use crate::app_config::app_path;
+use crate::fs_utils::{content_root, hash_file, hash_tree, replace_dir, walk_files};
+use crate::gamebanana::{fetch_mod, FetchError, RemoteFile};
+use crate::library_store::resolve_link;
+use crate::manifest::{is_imi_file, now_secs, read_manifest, write_manifest};
+use crate::mod_manager::{active_inis, is_ini, relative_path, scan_mods, InstalledMod};
+use crate::readme_detector::{collect_readmes, ModReadme};
+use crate::user_state::carry_over_ini_state;
+use crate::version_store::{load_versions, retain_version};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+
+/// Number of GameBanana requests in flight while checking for updates
+const UPDATE_CHECK_CONCURRENCY: usize = 4;
+/// Suffix for the new copy of a user-modified file under the `keepBoth` policy
+const UPDATE_COPY_SUFFIX: &str = ".update";
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateInfo {
+    pub path: String,
+    pub name: String,
+    pub mod_id: u64,
+    pub installed_file_id: Option<u64>,
+    pub installed_updated: u64,
+    pub remote_updated: u64,
+    pub installed_file_removed: bool,
+    pub new_files: Vec<RemoteFile>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheckError {
+    pub path: String,
+    pub name: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateCheckReport {
+    pub updates: Vec<ModUpdateInfo>,
+    pub withdrawn: Vec<ModUpdateInfo>,
+    pub failed: Vec<UpdateCheckError>,
+    pub up_to_date: usize,
+    /// Mods without a manifest or GameBanana source
+    pub unchecked: usize,
+}
+
+enum CheckOutcome {
+    Update(ModUpdateInfo),
+    Withdrawn(ModUpdateInfo),
+    UpToDate,
+    Failed(UpdateCheckError),
+}
+
+/// Compare one installed mod against its GameBanana page
+async fn check_mod(installed: InstalledMod, mod_id: u64) -> CheckOutcome {
+    let manifest = installed.manifest.unwrap_or_default();
+    // `updated` mirrors `_tsDateUpdated` at install time; older installs only know when they were installed
+    let baseline = if manifest.updated > 0 {
+        manifest.updated
+    } else {
+        manifest.installed
+    };
+    let mut info = ModUpdateInfo {
+        path: installed.path,
+        name: installed.name,
+        mod_id,
+        installed_file_id: manifest.file_id(),
+        installed_updated: baseline,
+        remote_updated: 0,
+        installed_file_removed: false,
+        new_files: Vec::new(),
+    };
+    let remote = match fetch_mod(mod_id).await {
+        Ok(remote) => remote,
+        Err(FetchError::Gone) => return CheckOutcome::Withdrawn(info),
+        Err(FetchError::Other(error)) => {
+            return CheckOutcome::Failed(UpdateCheckError {
+                path: info.path,
+                name: info.name,
+                error,
+            })
+        }
+    };
+    info.remote_updated = remote.date_updated;
+    if !remote.is_available() || remote.files.is_empty() {
+        return CheckOutcome::Withdrawn(info);
+    }
+    info.installed_file_removed = info
+        .installed_file_id
+        .is_some_and(|id| !remote.files.iter().any(|f| f.id == id));
+    info.new_files = remote
+        .files
+        .into_iter()
+        .filter(|f| f.added > baseline)
+        .collect();
+    if info.new_files.is_empty() {
+        CheckOutcome::UpToDate
+    } else {
+        CheckOutcome::Update(info)
+    }
+}
+
+/// Check every installed mod of a game with a GameBanana source for newer files
+#[tauri::command]
+pub async fn check_mod_updates(game: String) -> Result<UpdateCheckReport, String> {
+    let root = crate::app_config::mods_root(&game)?;
+    let mut report = UpdateCheckReport::default();
+    let mut to_check = Vec::new();
+    for installed in scan_mods(&root) {
+        match installed.manifest.as_ref().and_then(|m| m.mod_id()) {
+            Some(mod_id) => to_check.push((installed, mod_id)),
+            None => report.unchecked += 1,
+        }
+    }
+    println!("Checking {} mods of {} for updates", to_check.len(), game);
+
+    let mut outcomes = futures_util::stream::iter(to_check)
+        .map(|(installed, mod_id)| check_mod(installed, mod_id))
+        .buffer_unordered(UPDATE_CHECK_CONCURRENCY);
+    while let Some(outcome) = outcomes.next().await {
+        match outcome {
+            CheckOutcome::Update(info) => report.updates.push(info),
+            CheckOutcome::Withdrawn(info) => report.withdrawn.push(info),
+            CheckOutcome::UpToDate => report.up_to_date += 1,
+            CheckOutcome::Failed(error) => report.failed.push(error),
+        }
+    }
+    println!(
+        "Update check for {}: {} updates, {} withdrawn, {} failed",
+        game,
+        report.updates.len(),
+        report.withdrawn.len(),
+        report.failed.len()
+    );
+    Ok(report)
+}
+
+/// What to do with a file the user changed since install when the update ships it too
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub enum UpdatePolicy {
+    /// Keep the user's copy and drop the new one
+    #[default]
+    KeepUser,
+    /// Replace the user's copy with the new one
+    TakeNew,
+    /// Keep the user's copy and write the new one next to it with an `.update` suffix
+    KeepBoth,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ModUpdateResult {
+    pub path: String,
+    pub file_id: u64,
+    pub fname: String,
+    pub version: String,
+    pub added: Vec<String>,
+    pub replaced: Vec<String>,
+    pub removed: Vec<String>,
+    pub kept: Vec<String>,
+    pub conflicts: Vec<String>,
+    /// Readmes of the new version, converted to UTF-8 like on install
+    pub readmes: Vec<ModReadme>,
+}
+
+#[derive(PartialEq, Debug)]
+enum FileAction {
+    TakeNew,
+    KeepUser,
+    KeepBoth,
+    Remove,
+}
+
+/// Decide per file how to merge the installed mod with the new archive contents
+fn plan_update(
+    mod_dir: &Path,
+    shipped: &BTreeMap<String, String>,
+    new_root: &Path,
+    policies: &HashMap<String, UpdatePolicy>,
+    default_policy: UpdatePolicy,
+) -> Vec<(String, FileAction)> {
+    let current: BTreeSet<String> = walk_files(mod_dir)
+        .iter()
+        .map(|f| relative_path(mod_dir, f))
+        .collect();
+    let incoming: BTreeSet<String> = walk_files(new_root)
+        .iter()
+        .map(|f| relative_path(new_root, f))
+        .collect();
+
+    let mut plan = Vec::new();
+    for rel in current.union(&incoming) {
+        let in_new = incoming.contains(rel);
+        // Checked first: files only the update has, previews included, have nothing to keep
+        if !current.contains(rel) {
+            plan.push((rel.clone(), FileAction::TakeNew));
+            continue;
+        }
+        if is_imi_file(rel) {
+            plan.push((rel.clone(), FileAction::KeepUser));
+            continue;
+        }
+        let modified = match shipped.get(rel) {
+            Some(original) => hash_file(&mod_dir.join(rel)).ok().as_ref() != Some(original),
+            // Installs without recorded hashes: assume only ini files were edited
+            None if shipped.is_empty() && in_new => is_ini(Path::new(rel)),
+            // Not part of the installed archive: the user added it
+            None => true,
+        };
+        let action = match (modified, in_new) {
+            (false, true) => FileAction::TakeNew,
+            (false, false) => FileAction::Remove,
+            (true, false) => FileAction::KeepUser,
+            (true, true) => match policies.get(rel).copied().unwrap_or(default_policy) {
+                UpdatePolicy::KeepUser => FileAction::KeepUser,
+                UpdatePolicy::TakeNew => FileAction::TakeNew,
+                UpdatePolicy::KeepBoth => FileAction::KeepBoth,
+            },
+        };
+        plan.push((rel.clone(), action));
+    }
+    plan
+}
+
+fn copy_into(src: &Path, dest: &Path) -> Result<(), String> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::copy(src, dest)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to copy {:?} to {:?}: {}", src, dest, e))
+}
+
+/// Update an installed mod to a newer GameBanana file, keeping files the user changed
+#[tauri::command]
+pub async fn update_mod(
+    app_handle: tauri::AppHandle,
+    path: String,
+    file_id: Option<u64>,
+    policies: Option<HashMap<String, UpdatePolicy>>,
+    default_policy: Option<UpdatePolicy>,
+) -> Result<ModUpdateResult, String> {
+    // Library mods are updated in the library, behind their link
+    let mod_dir = &resolve_link(Path::new(&path));
+    crate::cold_storage::ensure_restored(&app_handle, mod_dir).await?;
+    let mut manifest =
+        read_manifest(mod_dir).ok_or_else(|| format!("No install manifest found in {}", path))?;
+    let mod_id = manifest
+        .mod_id()
+        .ok_or_else(|| format!("Mod at {} has no GameBanana source", path))?;
+    let remote = fetch_mod(mod_id).await.map_err(|e| e.to_string())?;
+    let file = match file_id {
+        Some(id) => remote.files.iter().find(|f| f.id == id),
+        None => remote.files.iter().max_by_key(|f| f.added),
+    }
+    .cloned()
+    .ok_or_else(|| format!("No matching file found for mod {}", mod_id))?;
+
+    let key = format!("update_{}_{}", mod_id, now_secs());
+    let staging = app_path("downloads").join(&key);
+    let result = async {
+        let archive =
+            crate::download_file(&app_handle, &file.download_url, &staging, "update", &key).await?;
+        let extracted = staging.join("extracted");
+        crate::decompress_file(
+            app_handle.clone(),
+            &archive.to_string_lossy(),
+            &extracted.to_string_lossy(),
+        )
+        .await?;
+        let new_root = content_root(&extracted);
+        // Converted before hashing, so the recorded hashes match the files as installed
+        let readmes = collect_readmes(&new_root, true);
+
+        let plan = plan_update(
+            mod_dir,
+            &manifest.files,
+            &new_root,
+            &policies.unwrap_or_default(),
+            default_policy.unwrap_or_default(),
+        );
+        let merged = staging.join("merged");
+        let mut outcome = ModUpdateResult {
+            path: path.clone(),
+            file_id: file.id,
+            fname: file.name.clone(),
+            readmes,
+            ..Default::default()
+        };
+        for (rel, action) in plan {
+            let existed = mod_dir.join(&rel).is_file();
+            match action {
+                FileAction::TakeNew => {
+                    copy_into(&new_root.join(&rel), &merged.join(&rel))?;
+                    if existed {
+                        outcome.replaced.push(rel);
+                    } else {
+                        outcome.added.push(rel);
+                    }
+                }
+                FileAction::KeepUser => {
+                    copy_into(&mod_dir.join(&rel), &merged.join(&rel))?;
+                    if !is_imi_file(&rel) {
+                        outcome.kept.push(rel);
+                    }
+                }
+                FileAction::KeepBoth => {
+                    copy_into(&mod_dir.join(&rel), &merged.join(&rel))?;
+                    let copy = format!("{}{}", rel, UPDATE_COPY_SUFFIX);
+                    copy_into(&new_root.join(&rel), &merged.join(&copy))?;
+                    outcome.conflicts.push(rel);
+                }
+                FileAction::Remove => outcome.removed.push(rel),
+            }
+        }
+
+        let record = retain_version(&app_handle, mod_dir, &manifest).await?;
+        outcome.version = record.map(|r| r.id).unwrap_or_default();
+        let old_inis = active_inis(mod_dir);
+        replace_dir(mod_dir, &merged)?;
+        carry_over_ini_state(mod_dir, &old_inis);
+
+        manifest.files = hash_tree(&new_root, is_imi_file);
+        manifest.file = file.download_url.clone();
+        manifest.fname = file.name.clone();
+        manifest.updated = remote.date_updated.max(file.added);
+        manifest.versions = load_versions(mod_dir, &manifest);
+        write_manifest(mod_dir, &manifest)?;
+        Ok::<_, String>(outcome)
+    }
+    .await;
+
+    if let Err(e) = std::fs::remove_dir_all(&staging) {
+        println!("Failed to clean update staging folder {:?}: {}", staging, e);
+    }
+    match &result {
+        Ok(outcome) => println!(
+            "Updated {}: {} added, {} replaced, {} removed, {} kept, {} conflicts",
+            path,
+            outcome.added.len(),
+            outcome.replaced.len(),
+            outcome.removed.len(),
+            outcome.kept.len(),
+            outcome.conflicts.len()
+        ),
+        Err(e) => println!("Update of {} failed: {}", path, e),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, rel: &str, data: &str) {
+        let path = dir.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, data).unwrap();
+    }
+
+    fn action_of<'a>(plan: &'a [(String, FileAction)], rel: &str) -> Option<&'a FileAction> {
+        plan.iter().find(|(r, _)| r == rel).map(|(_, a)| a)
+    }
+
+    #[test]
+    fn new_preview_is_taken_from_the_update() {
+        let mod_dir = tempfile::tempdir().unwrap();
+        let new_root = tempfile::tempdir().unwrap();
+        write(mod_dir.path(), "mod.ini", "[Constants]");
+        write(
+            mod_dir.path(),
+            "open_mod_page.html",
+            "url=https://gamebanana.com/mods/1",
+        );
+        write(new_root.path(), "mod.ini", "[Constants]");
+        write(new_root.path(), "preview.png", "png");
+        write(
+            new_root.path(),
+            "open_mod_page.html",
+            "url=https://gamebanana.com/mods/2",
+        );
+
+        let plan = plan_update(
+            mod_dir.path(),
+            &BTreeMap::new(),
+            new_root.path(),
+            &HashMap::new(),
+            UpdatePolicy::default(),
+        );
+        assert_eq!(action_of(&plan, "preview.png"), Some(&FileAction::TakeNew));
+        assert_eq!(
+            action_of(&plan, "open_mod_page.html"),
+            Some(&FileAction::KeepUser)
+        );
+    }
+}
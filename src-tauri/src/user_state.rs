@@ -0,0 +1,150 @@
+use crate::mod_manager::{active_inis, relative_path};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// File where XXMI persists `persist` variables, next to `d3dx.ini`
+const USER_INI: &str = "d3dx_user.ini";
+
+/// Importer folder a mod belongs to: the closest ancestor holding `d3dx_user.ini` or `d3dx.ini`
+fn importer_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.join(USER_INI).is_file() || dir.join("d3dx.ini").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Prefix of the persisted keys of everything under `path`, as XXMI writes it: `$\mods\<...>\`
+fn state_prefix(root: &Path, path: &Path) -> String {
+    format!(
+        "$\\{}\\",
+        relative_path(root, path)
+            .replace('/', "\\")
+            .to_ascii_lowercase()
+    )
+}
+
+fn key_of(line: &str) -> Option<&str> {
+    let (key, _) = line.split_once('=')?;
+    let key = key.trim();
+    key.starts_with("$\\").then_some(key)
+}
+
+fn starts_with_ignore_case(text: &str, prefix: &str) -> bool {
+    text.len() >= prefix.len()
+        && text.is_char_boundary(prefix.len())
+        && text[..prefix.len()].eq_ignore_ascii_case(prefix)
+}
+
+/// Rewrite `d3dx_user.ini` line by line; `edit` returns the replacement line, or `None` to drop it
+fn rewrite_user_ini(
+    root: &Path,
+    mut edit: impl FnMut(&str, &str) -> Option<String>,
+) -> Result<usize, String> {
+    let path = root.join(USER_INI);
+    let Ok(text) = std::fs::read_to_string(&path) else {
+        return Ok(0);
+    };
+    let mut changed = 0;
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        match key_of(line) {
+            Some(key) => match edit(line, key) {
+                Some(new_line) => {
+                    if new_line != line {
+                        changed += 1;
+                    }
+                    lines.push(new_line);
+                }
+                None => changed += 1,
+            },
+            None => lines.push(line.to_string()),
+        }
+    }
+    if changed == 0 {
+        return Ok(0);
+    }
+    let tmp = path.with_extension("ini.tmp");
+    std::fs::write(&tmp, lines.join("\r\n") + "\r\n")
+        .map_err(|e| format!("Failed to write {:?}: {}", tmp, e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to save {:?}: {}", path, e))?;
+    Ok(changed)
+}
+
+/// Move the saved variable state of a mod folder or ini from `old` to `new`
+pub fn migrate_user_state(old: &Path, new: &Path) -> Result<usize, String> {
+    if old == new {
+        return Ok(0);
+    }
+    let Some(root) = importer_root(new).or_else(|| importer_root(old)) else {
+        return Ok(0);
+    };
+    let old_prefix = state_prefix(&root, old);
+    let new_prefix = state_prefix(&root, new);
+    let moved = rewrite_user_ini(&root, |line, key| {
+        if !starts_with_ignore_case(key, &old_prefix) {
+            return Some(line.to_string());
+        }
+        let (_, value) = line.split_once('=').unwrap_or((line, ""));
+        Some(format!(
+            "{}{} ={}",
+            new_prefix,
+            &key[old_prefix.len()..],
+            value
+        ))
+    })?;
+    if moved > 0 {
+        println!(
+            "Moved {} saved variables from {:?} to {:?}",
+            moved, old, new
+        );
+    }
+    Ok(moved)
+}
+
+/// After an update or rollback, keep the state of a mod whose single ini was renamed
+pub fn carry_over_ini_state(mod_dir: &Path, old_inis: &[PathBuf]) {
+    let new_inis = active_inis(mod_dir);
+    let removed: Vec<&PathBuf> = old_inis.iter().filter(|i| !new_inis.contains(i)).collect();
+    let added: Vec<&PathBuf> = new_inis.iter().filter(|i| !old_inis.contains(i)).collect();
+    if let ([old], [new]) = (removed.as_slice(), added.as_slice()) {
+        if let Err(e) = migrate_user_state(old, new) {
+            println!(
+                "Failed to carry over saved variables of {:?}: {}",
+                mod_dir, e
+            );
+        }
+    }
+}
+
+/// Saved variable values of a mod, by key
+#[tauri::command]
+pub fn get_mod_user_state(path: String) -> BTreeMap<String, String> {
+    let mod_dir = Path::new(&path);
+    let Some(root) = importer_root(mod_dir) else {
+        return BTreeMap::new();
+    };
+    let prefix = state_prefix(&root, mod_dir);
+    std::fs::read_to_string(root.join(USER_INI))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(key, value)| (key.trim(), value.trim()))
+        .filter(|(key, _)| starts_with_ignore_case(key, &prefix))
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+/// Forget the saved variable state of a mod so XXMI falls back to the ini defaults
+#[tauri::command]
+pub fn reset_mod_user_state(path: String) -> Result<usize, String> {
+    let mod_dir = Path::new(&path);
+    let Some(root) = importer_root(mod_dir) else {
+        return Ok(0);
+    };
+    let prefix = state_prefix(&root, mod_dir);
+    let removed = rewrite_user_ini(&root, |line, key| {
+        (!starts_with_ignore_case(key, &prefix)).then(|| line.to_string())
+    })?;
+    println!("Reset {} saved variables of {}", removed, path);
+    Ok(removed)
+}
@@ -0,0 +1,222 @@
+use crate::app_config::{app_path, load_config};
+use crate::fs_utils::{copy_dir_all, replace_dir};
+use crate::library_store::library_root;
+use crate::manifest::{now_secs, read_manifest, write_manifest, ModManifest, VersionRecord};
+use crate::mod_manager::{enabled_name, mod_key};
+use std::path::{Path, PathBuf};
+
+const VERSIONS_DIR: &str = "versions";
+const INDEX_FILE: &str = "index.json";
+const DEFAULT_KEEP_VERSIONS: usize = 3;
+
+/// Game and key of a mod folder, from the Mods folder or library it sits in
+fn store_key(mod_dir: &Path, manifest: &ModManifest) -> (String, String) {
+    for (game, root) in load_config().paths {
+        if root.is_empty() {
+            continue;
+        }
+        for root in [PathBuf::from(root), library_root(&game)] {
+            if mod_dir.starts_with(&root) && mod_dir != root {
+                return (game, mod_key(&root, mod_dir));
+            }
+        }
+    }
+    let name = enabled_name(
+        mod_dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("mod"),
+    );
+    let game = if manifest.game.is_empty() {
+        "other".to_string()
+    } else {
+        manifest.game.clone()
+    };
+    (game, name)
+}
+
+/// Folder holding the retained versions of one mod, keyed by game and `mod_key`
+/// so variants from the same GameBanana page keep separate histories
+fn mod_store_dir(mod_dir: &Path, manifest: &ModManifest) -> PathBuf {
+    let (game, key) = store_key(mod_dir, manifest);
+    app_path(VERSIONS_DIR).join(game).join(key)
+}
+
+fn load_index(store: &Path) -> Vec<VersionRecord> {
+    std::fs::read_to_string(store.join(INDEX_FILE))
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(store: &Path, records: &[VersionRecord]) -> Result<(), String> {
+    let data = serde_json::to_string_pretty(records).map_err(|e| e.to_string())?;
+    std::fs::write(store.join(INDEX_FILE), data)
+        .map_err(|e| format!("Failed to write version index: {}", e))
+}
+
+fn remove_location(record: &VersionRecord) {
+    let location = Path::new(&record.location);
+    let result = if location.is_dir() {
+        std::fs::remove_dir_all(location)
+    } else {
+        std::fs::remove_file(location)
+    };
+    if let Err(e) = result {
+        println!("Failed to remove retained version {:?}: {}", location, e);
+    }
+}
+
+/// Retained versions of a mod, oldest first
+pub fn load_versions(mod_dir: &Path, manifest: &ModManifest) -> Vec<VersionRecord> {
+    load_index(&mod_store_dir(mod_dir, manifest))
+}
+
+/// Carry the retained versions of a mod over to its new key after a move or rename
+pub fn move_versions(game: &str, old_key: &str, new_key: &str) -> Result<(), String> {
+    let games = app_path(VERSIONS_DIR).join(game);
+    let (old_store, new_store) = (games.join(old_key), games.join(new_key));
+    if old_key == new_key || !old_store.is_dir() {
+        return Ok(());
+    }
+    if new_store.exists() {
+        return Err(format!("Versions for {} already exist", new_key));
+    }
+    crate::fs_utils::move_path(&old_store, &new_store)?;
+    let old_prefix = old_store.to_string_lossy().to_string();
+    let mut records = load_index(&new_store);
+    for record in &mut records {
+        if let Some(rest) = record.location.strip_prefix(&old_prefix) {
+            record.location = format!("{}{}", new_store.to_string_lossy(), rest);
+        }
+    }
+    save_index(&new_store, &records)
+}
+
+/// Store the current state of a mod before it gets replaced, dropping versions beyond the configured limit
+pub async fn retain_version(
+    app_handle: &tauri::AppHandle,
+    mod_dir: &Path,
+    manifest: &ModManifest,
+) -> Result<Option<VersionRecord>, String> {
+    let config = load_config();
+    let keep = config.keep_versions.unwrap_or(DEFAULT_KEEP_VERSIONS);
+    if keep == 0 {
+        return Ok(None);
+    }
+    let compressed = config.compress_versions.unwrap_or(false);
+    let store = mod_store_dir(mod_dir, manifest);
+    std::fs::create_dir_all(&store).map_err(|e| format!("Failed to create {:?}: {}", store, e))?;
+
+    let retained = now_secs();
+    let mut id = retained.to_string();
+    let mut counter = 1;
+    while store.join(&id).exists() || store.join(format!("{}.7z", id)).exists() {
+        id = format!("{}-{}", retained, counter);
+        counter += 1;
+    }
+    let location = if compressed {
+        let archive = store.join(format!("{}.7z", id));
+        crate::compress_dir(app_handle, mod_dir, &archive).await?;
+        archive
+    } else {
+        let folder = store.join(&id);
+        copy_dir_all(mod_dir, &folder)?;
+        folder
+    };
+    println!("Retained version {} of {:?} at {:?}", id, mod_dir, location);
+
+    let record = VersionRecord {
+        id,
+        file: manifest.file.clone(),
+        fname: manifest.fname.clone(),
+        updated: manifest.updated,
+        installed: manifest.installed,
+        retained,
+        location: location.to_string_lossy().to_string(),
+        compressed,
+    };
+    let mut records = load_index(&store);
+    records.push(record.clone());
+    while records.len() > keep {
+        remove_location(&records.remove(0));
+    }
+    save_index(&store, &records)?;
+    Ok(Some(record))
+}
+
+/// List the retained versions of an installed mod
+#[tauri::command]
+pub fn list_mod_versions(path: String) -> Result<Vec<VersionRecord>, String> {
+    let mod_dir = Path::new(&path);
+    let manifest = read_manifest(mod_dir).unwrap_or_default();
+    Ok(load_versions(mod_dir, &manifest))
+}
+
+/// Keep a copy of a mod that is about to be overwritten by a reinstall
+#[tauri::command]
+pub async fn retain_mod_version(
+    app_handle: tauri::AppHandle,
+    path: String,
+) -> Result<Option<VersionRecord>, String> {
+    let mod_dir = Path::new(&path);
+    if !mod_dir.is_dir() {
+        return Ok(None);
+    }
+    let manifest = read_manifest(mod_dir).unwrap_or_default();
+    retain_version(&app_handle, mod_dir, &manifest).await
+}
+
+/// Restore a retained version of a mod, keeping the current one as a version in turn
+#[tauri::command]
+pub async fn rollback_mod(
+    app_handle: tauri::AppHandle,
+    path: String,
+    version: String,
+) -> Result<ModManifest, String> {
+    let mod_dir = &crate::library_store::resolve_link(Path::new(&path));
+    crate::cold_storage::ensure_restored(&app_handle, mod_dir).await?;
+    let current = read_manifest(mod_dir).unwrap_or_default();
+    let store = mod_store_dir(mod_dir, &current);
+    let record = load_index(&store)
+        .into_iter()
+        .find(|r| r.id == version)
+        .ok_or_else(|| format!("Version '{}' not found for {}", version, path))?;
+
+    let staging = app_path("downloads").join(format!("rollback_{}", record.id));
+    let _ = std::fs::remove_dir_all(&staging);
+    if record.compressed {
+        crate::decompress_file(
+            app_handle.clone(),
+            &record.location,
+            &staging.to_string_lossy(),
+        )
+        .await?;
+    } else {
+        copy_dir_all(Path::new(&record.location), &staging)?;
+    }
+
+    retain_version(&app_handle, mod_dir, &current).await?;
+    let old_inis = crate::mod_manager::active_inis(mod_dir);
+    let result = replace_dir(mod_dir, &staging);
+    let _ = std::fs::remove_dir_all(&staging);
+    result?;
+    crate::user_state::carry_over_ini_state(mod_dir, &old_inis);
+
+    // The restored version is live again, so it no longer needs a retained copy
+    let mut records = load_index(&store);
+    if let Some(idx) = records.iter().position(|r| r.id == record.id) {
+        remove_location(&records.remove(idx));
+        save_index(&store, &records)?;
+    }
+
+    let mut manifest = read_manifest(mod_dir).unwrap_or_else(|| current.clone());
+    manifest.file = record.file;
+    manifest.fname = record.fname;
+    manifest.updated = record.updated;
+    manifest.installed = record.installed;
+    manifest.versions = records;
+    write_manifest(mod_dir, &manifest)?;
+    println!("Rolled back {} to version {}", path, version);
+    Ok(manifest)
+}
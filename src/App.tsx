@@ -65,6 +65,7 @@ function App() {
 	const categories = useAtomValue(CATEGORIES);
 	const config = useAtomValue(CONFIG);
 	const [urlQueue, setUrlQueue] = useState<string[]>([]);
+	const [itemQueue, setItemQueue] = useState<DownloadItem[]>([]);
 	const [pendingActions, setPendingActions] = useState<PendingAction[]>([]);
 	const [downloads, setDownloads] = useAtom(DOWNLOAD_LIST);
 	const elementRefs = useRef<{
@@ -116,6 +117,7 @@ function App() {
 				file,
 				fname,
 				name: sanitizeFileName(item._sName),
+				updated: item._tsDateUpdated || 0,
 			};
 		}
 
@@ -202,15 +204,6 @@ function App() {
 			lastUpdate: Date.now(),
 		};
 		console.log(`Starting download for ${item.name} with key ${item.key} from ${item.file}`);
-		if (item?.preview && config.preview) {
-			invoke("download_and_unzip", {
-				fileName: "preview",
-				downloadUrl: item.preview,
-				savePath: item.gamePath,
-				key: item.key,
-				emit: false,
-			});
-		}
 		const downloads = JSON.parse(sessionStorage.getItem("downloads") || "{}");
 		downloads[item.key] = {
 			...item,
@@ -348,6 +341,11 @@ function App() {
 				sessionStorage.setItem("downloads", JSON.stringify(downloads));
 			}
 		});
+		listen("queue-downloads", (event) => {
+			const items = event.payload as DownloadItem[];
+			console.log(`[IMM] Queueing ${items.length} downloads from the backend`);
+			setItemQueue((prev) => [...prev, ...items]);
+		});
 		listen("fin", async (event) => {
 			const payload = event.payload as any;
 			const key = payload.key as string;
@@ -364,6 +362,7 @@ function App() {
 				if (finishedElement?.key) {
 					finishedElement.status = "completed";
 					finishedElement.categorized = store.get(CONFIG).categorized;
+					finishedElement.readmes = payload.readmes || [];
 					prev.completed.push({
 						...finishedElement,
 					});
@@ -394,6 +393,12 @@ function App() {
 			setUrlQueue([]);
 		}
 	}, [urlQueue]);
+	useEffect(() => {
+		if (itemQueue.length > 0) {
+			itemQueue.forEach((item) => addToDownloads(item.file, item));
+			setItemQueue([]);
+		}
+	}, [itemQueue]);
 	const renderPage = () => {
 		switch (currentPage) {
 			case "settings":
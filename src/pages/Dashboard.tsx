@@ -1,9 +1,10 @@
 import { addToast } from "@/_Toaster/ToastProvider";
 import { Button } from "@/components/ui/button";
 import { Label } from "@/components/ui/label";
+import { error } from "@/lib/logger";
 import { GAME_ICONS, GAME_NAMES } from "@/utils/consts";
 import { Games } from "@/utils/types";
-import { join, serializeDownloads } from "@/utils/utils";
+import { join, serializeDownloads, thumbnailUrl } from "@/utils/utils";
 import { CONFIG, DOWNLOAD_LIST } from "@/utils/vars";
 import { invoke } from "@tauri-apps/api/core";
 import { exists } from "@tauri-apps/plugin-fs";
@@ -21,6 +22,7 @@ import {
 	SquareIcon,
 	XIcon,
 } from "lucide-react";
+import { useEffect, useState } from "react";
 
 const Icons = {
 	pending: <ClockIcon className="min-h-4 min-w-4 max-w-4" />,
@@ -29,6 +31,32 @@ const Icons = {
 	failed: <XIcon className="min-h-4 min-w-4 max-w-4 text-destructive" />,
 	extracting: <FolderArchiveIcon className="min-h-4 min-w-4 max-w-4 animate-pulse" />,
 };
+/** Mod preview served as a cached thumbnail by the local image server */
+function Preview({ source }: { source: string }) {
+	const [src, setSrc] = useState("");
+	useEffect(() => {
+		let active = true;
+		setSrc("");
+		if (source) {
+			thumbnailUrl(source, 96)
+				.then((url) => active && setSrc(url))
+				.catch((err) => error("[IMM] Error loading preview thumbnail:", err));
+		}
+		return () => {
+			active = false;
+		};
+	}, [source]);
+	return src ? (
+		<img
+			src={src}
+			onError={() => setSrc("")}
+			className="min-w-12 w-12 h-12 object-cover rounded"
+			alt="preview"
+		/>
+	) : (
+		<div className="min-w-12 w-12 h-12 bg-background/20 rounded" />
+	);
+}
 export default function Dashboard({
 	elementRefs,
 	prev,
@@ -120,6 +148,7 @@ export default function Dashboard({
 							)}
 							<div className=" flex items-center flex-1 w-full gap-3">
 								{Icons[item.status as keyof typeof Icons] || <FileQuestionIcon className="min-h-4 min-w-4" />}
+								<Preview source={item.preview} />
 								<div className="flex flex-col flex-1 w-full">
 									<div className="flex items-center gap-1">
 										<Label
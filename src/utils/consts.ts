@@ -29,7 +29,6 @@ export const GAME_ICONS: { [key in Games]: string } = {
 	EF: "https://images.gamebanana.com/img/ico/games/69725f15986b1.png",
 };
 export const exts = ["png", "jpg", "jpeg", "webp", "gif"];
-export const PRIORITY_KEYS = ["Alt", "Ctrl", "Shift", "Capslock", "Tab", "Up", "Down", "Left", "Right"] as const;
 
 export const GAME_ID_MAP: { [key: string]: number } = {
 	WW: 0,
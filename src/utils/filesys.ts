@@ -1,12 +1,13 @@
-import { open } from "@tauri-apps/plugin-dialog";
+import { ask, open } from "@tauri-apps/plugin-dialog";
 import { copyFile, exists, mkdir, readDir, remove, rename, writeTextFile } from "@tauri-apps/plugin-fs";
 import { join } from "./utils";
-import { error, info } from "@/lib/logger";
+import { error, info, warn } from "@/lib/logger";
 import { exts, GAME_NAMES, UNCATEGORIZED } from "./consts";
 import { CONFIG, DOWNLOAD_LIST, store } from "./vars";
 import { openPath } from "@tauri-apps/plugin-opener";
 import { invoke } from "@tauri-apps/api/core";
-import { DownloadItem } from "./types";
+import { DownloadItem, Games } from "./types";
+import { addToast } from "@/_Toaster/ToastProvider";
 
 export async function selectPath(
 	options = { multiple: false, directory: false } as {
@@ -62,7 +63,9 @@ export async function getTypesFromPath(path: string) {
 	const data = {} as any;
 	let entries = await readDir(path);
 	data.entries = entries;
-	data.txtCount = entries.filter((entry) => entry.name.endsWith(".txt") && !entry.isDirectory).length;
+	data.txtCount = entries.filter(
+		(entry) => /\.(txt|md|nfo)$/i.test(entry.name) && !entry.isDirectory
+	).length;
 	data.imgCount = entries.filter((entry: any) => {
 		const ext = entry.name.split(".").slice(-1)[0].toLowerCase();
 		return exts.includes(ext) && !entry.isDirectory;
@@ -71,6 +74,30 @@ export async function getTypesFromPath(path: string) {
 	data.dirs = entries.filter((entry) => entry.isDirectory);
 	return data;
 }
+/**
+ * Ask whether a mod that looks like it belongs to another game should be installed there instead
+ */
+async function confirmModGame(item: DownloadItem, detected: Games) {
+	const config = store.get(CONFIG);
+	const detectedName = GAME_NAMES[detected] || detected;
+	const message = `${item.name} looks like a ${detectedName} mod, not ${GAME_NAMES[item.game]}.`;
+	warn("[IMM]", message);
+	const detectedPath = config.paths[detected];
+	if (!detectedPath || !(await exists(detectedPath))) {
+		addToast({ type: "warning", message: `${message} Set a ${detectedName} Mods folder to install it there.`, duration: 6000 });
+		return;
+	}
+	const switchGame = await ask(`${message}\n\nInstall it into the ${detectedName} Mods folder instead?`, {
+		title: "Possible wrong game",
+		kind: "warning",
+		okLabel: `Install for ${detectedName}`,
+		cancelLabel: `Keep ${GAME_NAMES[item.game]}`,
+	});
+	if (!switchGame) return;
+	info(`[IMM] Installing ${item.name} for ${detectedName} instead of ${GAME_NAMES[item.game]}`);
+	item.game = detected;
+	item.gamePath = detectedPath;
+}
 export async function validateModDownload(item: DownloadItem) {
 	const config = store.get(CONFIG);
 	let path = join("downloads", item.key);
@@ -91,16 +118,55 @@ export async function validateModDownload(item: DownloadItem) {
 			}
 			data = await getTypesFromPath(path);
 		}
+		if (item.game) {
+			const check: any = await invoke("check_mod_game", { path, game: item.game }).catch((err) => {
+				error("[IMM] Error checking mod game:", err);
+				return null;
+			});
+			if (check && !check.matches && check.detected) await confirmModGame(item, check.detected);
+		}
 		if (!item.category) item.category = UNCATEGORIZED;
 		if (!item.name) item.name = "Mod_" + Date.now().toString();
 		if (!(await exists(item.gamePath))) return;
 		const base = item.categorized ? join(item.gamePath, item.category) : item.gamePath;
 		const dest = join(base, item.name);
 		await mkdir(base, { recursive: true });
+		if (await exists(dest))
+			await invoke("retain_mod_version", { path: dest }).catch((err) =>
+				error("[IMM] Error keeping previous mod version:", err)
+			);
 		try {
 			await remove(dest, { recursive: true });
 		} catch {}
 		await rename(path, dest);
+		await invoke("save_mod_manifest", {
+			path: dest,
+			manifest: {
+				name: item.name,
+				game: item.game,
+				category: item.category,
+				source: item.source,
+				file: item.file,
+				fname: item.fname,
+				preview: item.preview,
+				updated: item.updated || 0,
+			},
+		}).catch((err) => error("[IMM] Error saving mod manifest:", err));
+		const gallery: any[] =
+			config.preview && item.source
+				? await invoke<any[]>("download_mod_gallery", { path: dest }).catch((err) => {
+						error("[IMM] Error downloading mod gallery:", err);
+						return [];
+					})
+				: [];
+		if (!gallery.length)
+			await invoke("generate_mod_preview", { path: dest, force: false }).catch((err) =>
+				error("[IMM] Error generating mod preview:", err)
+			);
+		if (config.libraryMode)
+			await invoke("add_to_library", { game: item.game, paths: [dest] }).catch((err) =>
+				error("[IMM] Error moving mod into the library:", err)
+			);
 		if (config.source) {
 			// const fileData = `Name: ${item.name}\nMod Link: ${item.source}\nFile Link: ${item.file}\nPreview Link: ${item.preview}\nInstalled At: ${formatDateTime()}`;
 			const newFileData = `<!DOCTYPE html>
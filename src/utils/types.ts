@@ -37,6 +37,14 @@ export interface DownloadItem {
 	key: string;
 	gamePath:string;
 	categorized?: boolean;
+	updated?: number;
+	readmes?: ModReadme[];
+}
+export interface ModReadme {
+	file: string;
+	kind: "readme" | "instructions" | "credits";
+	encoding: string;
+	content: string;
 }
 export interface DownloadList {
 	queue: DownloadItem[];
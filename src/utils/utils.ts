@@ -1,3 +1,4 @@
+import { invoke } from "@tauri-apps/api/core";
 import { apiClient } from "./api";
 
 export function serializeDownloads(downloads: any) {
@@ -13,6 +14,18 @@ export function serializeDownloads(downloads: any) {
 	return downloadList;
 }
 
+let imageServer: Promise<{ baseUrl: string; token: string }> | null = null;
+/** Url of a resized, cached thumbnail of a local image or a GameBanana image */
+export async function thumbnailUrl(source: string, size = 256) {
+	imageServer ??= invoke<{ baseUrl: string; token: string }>("get_image_server").catch((err) => {
+		imageServer = null;
+		throw err;
+	});
+	const { baseUrl, token } = await imageServer;
+	const param = /^https?:\/\//.test(source) ? "url" : "path";
+	return `${baseUrl}/thumb?token=${token}&${param}=${encodeURIComponent(source)}&size=${size}`;
+}
+
 export function join(...parts: string[]) {
 	let result = parts.filter((part) => part !== "").join("\\").replace("/", "\\").replaceAll("\\\\", "\\");
 	result = result.endsWith("\\") ? result.slice(0, -1) : result;